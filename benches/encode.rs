@@ -0,0 +1,116 @@
+//! Encoding benchmarks, codec2 against `alloy-sol-types` on the same values.
+//!
+//! To compare with another revision of the encoder, save a baseline there and compare to it:
+//!
+//! ```text
+//! cargo bench --bench encode -- --save-baseline before
+//! cargo bench --bench encode -- --baseline before
+//! ```
+use alloy_primitives::{Address, U256};
+use alloy_sol_types::SolValue;
+use byteorder::{BE, LE};
+use bytes::BytesMut;
+use codec2::{Encoder, FluentABI, SolidityABI};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use hashbrown::HashMap;
+
+type Record = (u64, String, Vec<U256>, Address);
+
+fn u256_values(count: usize) -> Vec<U256> {
+    (0..count).map(|i| U256::from(i) << 128).collect()
+}
+
+fn strings(count: usize) -> Vec<String> {
+    (0..count).map(|i| "codec".repeat(i % 16 + 1)).collect()
+}
+
+fn nested(count: usize) -> Vec<Vec<Vec<u32>>> {
+    (0..count)
+        .map(|i| (0..8).map(|j| (0..(i + j) as u32 % 8).collect()).collect())
+        .collect()
+}
+
+fn records(count: usize) -> Vec<Record> {
+    (0..count)
+        .map(|i| {
+            (
+                i as u64,
+                "record".repeat(i % 4 + 1),
+                u256_values(i % 8),
+                Address::repeat_byte(i as u8),
+            )
+        })
+        .collect()
+}
+
+/// Benchmarks Solidity encoding of the value with codec2 and `alloy-sol-types`.
+fn bench_sol<T>(c: &mut Criterion, name: &str, size: usize, value: &T)
+where
+    T: Encoder<BE, 32, true> + SolValue,
+{
+    let mut group = c.benchmark_group(name);
+    group.bench_with_input(BenchmarkId::new("codec2", size), value, |b, value| {
+        b.iter(|| {
+            let mut buf = BytesMut::new();
+            SolidityABI::encode(black_box(value), &mut buf, 0).unwrap();
+            buf
+        })
+    });
+    group.bench_with_input(BenchmarkId::new("alloy", size), value, |b, value| {
+        b.iter(|| black_box(value).abi_encode())
+    });
+    group.finish();
+}
+
+/// Benchmarks Fluent encoding of the value, there is no `alloy-sol-types` counterpart.
+fn bench_fluent<T>(c: &mut Criterion, name: &str, size: usize, value: &T)
+where
+    T: Encoder<LE, 4, false>,
+{
+    let mut group = c.benchmark_group(name);
+    group.bench_with_input(BenchmarkId::new("codec2", size), value, |b, value| {
+        b.iter(|| {
+            let mut buf = BytesMut::new();
+            FluentABI::encode(black_box(value), &mut buf, 0).unwrap();
+            buf
+        })
+    });
+    group.finish();
+}
+
+/// Benchmarks Fluent decoding of the encoded value.
+fn bench_fluent_decode<T>(c: &mut Criterion, name: &str, size: usize, value: &T)
+where
+    T: Encoder<LE, 4, false>,
+{
+    let mut buf = BytesMut::new();
+    FluentABI::encode(value, &mut buf, 0).unwrap();
+    let buf = buf.freeze();
+
+    let mut group = c.benchmark_group(name);
+    group.bench_with_input(BenchmarkId::new("codec2", size), &buf, |b, buf| {
+        b.iter(|| FluentABI::<T>::decode(black_box(buf), 0).unwrap())
+    });
+    group.finish();
+}
+
+fn encode_benchmarks(c: &mut Criterion) {
+    for size in [16, 1024] {
+        bench_sol(c, "sol_vec_u256", size, &u256_values(size));
+        bench_sol(c, "sol_vec_string", size, &strings(size));
+        bench_sol(c, "sol_nested_vec", size, &nested(size));
+        bench_sol(c, "sol_vec_tuple", size, &records(size));
+
+        bench_fluent(c, "fluent_vec_u256", size, &u256_values(size));
+        let numbers: Vec<u64> = (0..size as u64).collect();
+        bench_fluent(c, "fluent_vec_u64", size, &numbers);
+        bench_fluent_decode(c, "fluent_decode_vec_u64", size, &numbers);
+        bench_fluent(c, "fluent_vec_string", size, &strings(size));
+        bench_fluent(c, "fluent_nested_vec", size, &nested(size));
+        let map: HashMap<u32, Vec<u32>> = (0..size as u32).map(|i| (i, vec![i; 4])).collect();
+        bench_fluent(c, "fluent_map", size, &map);
+    }
+}
+
+criterion_group!(benches, encode_benchmarks);
+criterion_main!(benches);
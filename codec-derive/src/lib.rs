@@ -2,6 +2,7 @@ use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
 use syn::{
     parse_macro_input,
+    parse_quote,
     Data,
     DeriveInput,
     Fields,
@@ -48,15 +49,54 @@ impl CodecStruct {
         }
     }
 
-    fn generate_impl_sol(&self) -> TokenStream {
-        let struct_name = &self.struct_name;
-        let (_impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+    /// Generics of the struct extended with the byte order and the alignment of the encoder.
+    fn encoder_generics(&self) -> syn::Generics {
+        let mut generics = self.generics.clone();
+        generics.params.push(parse_quote!(B: ByteOrder));
+        generics.params.push(parse_quote!(const ALIGN: usize));
+        generics
+    }
+
+    /// Encoder generics together with the lifetime of the input buffer for `DecodeBorrowed`. It's
+    /// the first lifetime of the struct, the struct's other lifetimes must be outlived by it.
+    fn borrowed_generics(&self) -> (syn::Generics, syn::Lifetime) {
+        let mut generics = self.encoder_generics();
+        let mut lifetimes = self.generics.lifetimes().map(|def| def.lifetime.clone());
+
+        let lifetime = match lifetimes.next() {
+            Some(lifetime) => lifetime,
+            None => {
+                let lifetime: syn::Lifetime = parse_quote!('__de);
+                generics.params.insert(0, parse_quote!(#lifetime));
+                lifetime
+            }
+        };
+
+        for other in lifetimes {
+            generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#lifetime: #other));
+        }
+
+        (generics, lifetime)
+    }
 
-        let crate_name = if std::env::var("CARGO_PKG_NAME").unwrap() == "codec2" {
+    fn crate_name() -> TokenStream {
+        if std::env::var("CARGO_PKG_NAME").unwrap() == "codec2" {
             quote! { crate }
         } else {
             quote! { ::codec2 }
-        };
+        }
+    }
+
+    fn generate_impl_sol(&self) -> TokenStream {
+        let struct_name = &self.struct_name;
+        let encoder_generics = self.encoder_generics();
+        let (impl_generics, _, _) = encoder_generics.split_for_impl();
+        let (_, ty_generics, where_clause) = self.generics.split_for_impl();
+
+        let crate_name = Self::crate_name();
         
         let header_sizes = self.fields.iter().map(|field| {
             let ty = &field.ty;
@@ -122,6 +162,33 @@ impl CodecStruct {
             0 #( + #aligned_header_size)*
         };
 
+        let tail_lens = self.fields.iter().map(|field| {
+            let ident = &field.ident;
+            let ty = &field.ty;
+            quote! {
+                #crate_name::encoded_tail_len::<#ty, B, ALIGN, {true}>(&self.#ident)
+            }
+        });
+
+        let encode_zero_fields = self.fields.iter().map(|field| {
+            let ty = &field.ty;
+            quote! {
+                <#ty as #crate_name::Encoder<B, ALIGN, {true}>>::encode_zero(&mut tmp, current_offset)?;
+                current_offset += if <#ty as #crate_name::Encoder<B, ALIGN, {true}>>::IS_DYNAMIC {
+                    32
+                } else {
+                    align_up::<ALIGN>(<#ty as #crate_name::Encoder<B, ALIGN, {true}>>::HEADER_SIZE)
+                };
+            }
+        });
+
+        let zero_tail_lens = self.fields.iter().map(|field| {
+            let ty = &field.ty;
+            quote! {
+                #crate_name::zero_tail_len::<#ty, B, ALIGN, {true}>()
+            }
+        });
+
         let struct_initialization = self.fields.iter().map(|field| {
             let ident = &field.ident;
             quote! {
@@ -130,11 +197,11 @@ impl CodecStruct {
         });
 
         quote! {
-            impl<B: ByteOrder, const ALIGN: usize> #crate_name::Encoder<B, ALIGN, {true}> for #struct_name #ty_generics #where_clause {
+            impl #impl_generics #crate_name::Encoder<B, ALIGN, {true}> for #struct_name #ty_generics #where_clause {
                 const HEADER_SIZE: usize = 0 #( + #header_sizes)*;
                 const IS_DYNAMIC: bool = #is_dynamic;
 
-                fn encode(&self, buf: &mut ::bytes::BytesMut, offset: usize) -> Result<(), CodecError> {
+                fn encode(&self, buf: &mut impl #crate_name::EncodeSink, offset: usize) -> Result<(), CodecError> {
 
                     let aligned_offset = align_up::<ALIGN>(offset);
                     let is_dynamic = <Self as #crate_name::Encoder<B, ALIGN, { true }>>::IS_DYNAMIC;
@@ -143,36 +210,76 @@ impl CodecStruct {
 
                     // Write the dynamic struct offset if necessary
                     if is_dynamic {
-                        let buf_len = buf.len();
+                        let buf_len = #crate_name::EncodeSink::len(buf);
                         let offset = if buf_len == 0 {
                             32
                         } else {
                             buf_len
                         };
-                        write_u32_aligned::<B, ALIGN>(buf, aligned_offset, offset as u32);
+                        #crate_name::write_u32_aligned::<B, ALIGN>(buf, aligned_offset, offset as u32)?;
                     }
 
-                    // Create a new BytesMut for temporary storage
-                    let mut tmp = BytesMut::zeroed(aligned_header_size);
-                    let mut current_offset = 0; // Start at 0 for tmp buffer
+                    // Fields are encoded in place at the end of the buffer, relative to their start
+                    let base = #crate_name::EncodeSink::len(buf);
+                    #crate_name::EncodeSink::resize_zeroed(buf, base + aligned_header_size)?;
+                    let mut tmp = #crate_name::SubSink::new(buf, base);
+                    let mut current_offset = 0; // Start at 0 for the fields
 
                     // Encode static fields
                     #( #encode_fields )*
 
-                    buf.extend_from_slice(&tmp);
+                    Ok(())
+                }
+
+                fn encoded_len(&self) -> usize {
+                    // The offset word of a dynamic struct, then the fields
+                    let offset_size = if #is_dynamic { 32 } else { 0 };
+                    offset_size + #aligned_header_size #( + #tail_lens)*
+                }
+
+                fn encode_zero(buf: &mut impl #crate_name::EncodeSink, offset: usize) -> Result<(), CodecError> {
+                    if !(#is_dynamic) {
+                        return #crate_name::encode_zeroed::<Self, B, ALIGN, { true }>(buf, offset);
+                    }
+
+                    // Write the offset of the fields, they hold the zero values of their types
+                    #crate_name::ensure_buf_size(buf, offset + 32)?;
+                    let base = #crate_name::EncodeSink::len(buf);
+                    #crate_name::write_u32_aligned::<B, ALIGN>(buf, offset, base as u32)?;
+
+                    #crate_name::EncodeSink::resize_zeroed(buf, base + #aligned_header_size)?;
+                    let mut tmp = #crate_name::SubSink::new(buf, base);
+                    let mut current_offset = 0;
+
+                    #( #encode_zero_fields )*
 
                     Ok(())
                 }
 
-                fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+                fn zero_encoded_len() -> usize {
+                    if !(#is_dynamic) {
+                        return #crate_name::zeroed_len::<Self, B, ALIGN, { true }>();
+                    }
+
+                    32 + #aligned_header_size #( + #zero_tail_lens)*
+                }
+
+                fn decode(buf: &impl ::bytes::Buf, offset: usize) -> Result<Self, CodecError> {
                     let mut aligned_offset = align_up::<ALIGN>(offset);
 
-                    let mut tmp = if #is_dynamic {
-                        let offset = read_u32_aligned::<B, ALIGN>(&buf.chunk(), aligned_offset)? as usize;
-                        &buf.chunk()[offset..]
+                    let data = #crate_name::contiguous(buf)?;
+                    let struct_offset = if #is_dynamic {
+                        read_u32_aligned::<B, ALIGN>(&data.as_ref(), aligned_offset)? as usize
                     } else {
-                        &buf.chunk()[aligned_offset..]
+                        aligned_offset
                     };
+                    let mut tmp = data.get(struct_offset..).ok_or(CodecError::Decoding(
+                        #crate_name::DecodingError::BufferTooSmall {
+                            expected: struct_offset,
+                            found: data.len(),
+                            msg: "buf too small to take struct offset".into(),
+                        },
+                    ))?;
 
                     let mut current_offset = 0;
 
@@ -183,7 +290,7 @@ impl CodecStruct {
                     })
                 }
 
-                fn partial_decode(buffer: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+                fn partial_decode(buffer: &impl ::bytes::Buf, offset: usize) -> Result<(usize, usize), CodecError> {
                     Ok((0,0))
                 }
             }
@@ -192,13 +299,11 @@ impl CodecStruct {
 
     fn generate_impl_wasm(&self) -> TokenStream {
         let struct_name = &self.struct_name;
-        let (_impl_generics, ty_generics, where_clause) = self.generics.split_for_impl();
+        let encoder_generics = self.encoder_generics();
+        let (impl_generics, _, _) = encoder_generics.split_for_impl();
+        let (_, ty_generics, where_clause) = self.generics.split_for_impl();
 
-        let crate_name = if std::env::var("CARGO_PKG_NAME").unwrap() == "codec2" {
-            quote! { crate }
-        } else {
-            quote! { ::codec2 }
-        };
+        let crate_name = Self::crate_name();
 
         let header_sizes = self.fields.iter().map(|field| {
             let ty = &field.ty;
@@ -237,6 +342,14 @@ impl CodecStruct {
             }
         });
 
+        let tail_lens = self.fields.iter().map(|field| {
+            let ident = &field.ident;
+            let ty = &field.ty;
+            quote! {
+                #crate_name::encoded_tail_len::<#ty, B, ALIGN, {false}>(&self.#ident)
+            }
+        });
+
         let struct_initialization = self.fields.iter().map(|field| {
             let ident = &field.ident;
             quote! {
@@ -245,24 +358,28 @@ impl CodecStruct {
         });
 
         quote! {
-            impl<B:ByteOrder, const ALIGN: usize> #crate_name::Encoder<B, ALIGN, {false}> for #struct_name #ty_generics #where_clause {
+            impl #impl_generics #crate_name::Encoder<B, ALIGN, {false}> for #struct_name #ty_generics #where_clause {
                 const HEADER_SIZE: usize = 0 #( + #header_sizes)*;
                 const IS_DYNAMIC: bool = #is_dynamic;
 
-                fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
+                fn encode(&self, buf: &mut impl #crate_name::EncodeSink, offset: usize) -> Result<(), CodecError> {
                     let mut current_offset = align_up::<ALIGN>(offset);
                     let header_size = <Self as #crate_name::Encoder<B, ALIGN, { false }>>::HEADER_SIZE;
 
-                    if buf.len() < current_offset + header_size {
-                        buf.resize(current_offset + header_size, 0);
-                    }
+                    #crate_name::ensure_buf_size(buf, current_offset + header_size)?;
 
                     #( #encode_fields )*
 
                     Ok(())
                 }
 
-                fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+                fn encoded_len(&self) -> usize {
+                    <Self as #crate_name::Encoder<B, ALIGN, { false }>>::HEADER_SIZE #( + #tail_lens)*
+                }
+
+                fn decode(buf: &impl ::bytes::Buf, offset: usize) -> Result<Self, CodecError> {
+                    let data = #crate_name::contiguous(buf)?;
+                    let buf = &data.as_ref();
                     let mut current_offset = align_up::<ALIGN>(offset);
 
                     #( #decode_fields )*
@@ -272,21 +389,190 @@ impl CodecStruct {
                     })
                 }
 
-                fn partial_decode(buffer: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+                fn partial_decode(buffer: &impl ::bytes::Buf, offset: usize) -> Result<(usize, usize), CodecError> {
                     Ok((0,0))
                 }
             }
         }
     }
+
+    fn generate_impl_borrowed(&self, sol_mode: bool) -> TokenStream {
+        let struct_name = &self.struct_name;
+        let (borrowed_generics, lifetime) = self.borrowed_generics();
+        let (impl_generics, _, where_clause) = borrowed_generics.split_for_impl();
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        let crate_name = Self::crate_name();
+
+        let encoder = quote! { #crate_name::Encoder<B, ALIGN, {#sol_mode}> };
+        let decode_borrowed = quote! { #crate_name::DecodeBorrowed<#lifetime, B, ALIGN, {#sol_mode}> };
+
+        // Field offsets mirror `Encoder::decode` of the same mode
+        let decode_fields = self.fields.iter().map(|field| {
+            let ident = &field.ident;
+            let ty = &field.ty;
+            let field_size = if sol_mode {
+                quote! {
+                    if <#ty as #encoder>::IS_DYNAMIC {
+                        32
+                    } else {
+                        #crate_name::align_up::<ALIGN>(<#ty as #encoder>::HEADER_SIZE)
+                    }
+                }
+            } else {
+                quote! {
+                    #crate_name::align_up::<ALIGN>(<#ty as #encoder>::HEADER_SIZE)
+                }
+            };
+            quote! {
+                let #ident = <#ty as #decode_borrowed>::decode_borrowed(tmp, current_offset)?;
+                current_offset += #field_size;
+            }
+        });
+
+        // Solidity structs with dynamic fields are stored at the offset written in their header
+        let data = if sol_mode {
+            quote! {
+                let aligned_offset = #crate_name::align_up::<ALIGN>(offset);
+                let data_offset = if <Self as #encoder>::IS_DYNAMIC {
+                    #crate_name::read_u32_aligned::<B, ALIGN>(&buf, aligned_offset)? as usize
+                } else {
+                    aligned_offset
+                };
+                let tmp = buf.get(data_offset..).ok_or(#crate_name::CodecError::Decoding(
+                    #crate_name::DecodingError::UnexpectedEof,
+                ))?;
+                let mut current_offset = 0;
+            }
+        } else {
+            quote! {
+                let tmp = buf;
+                let mut current_offset = #crate_name::align_up::<ALIGN>(offset);
+            }
+        };
+
+        let struct_initialization = self.fields.iter().map(|field| &field.ident);
+
+        quote! {
+            impl #impl_generics #decode_borrowed for #struct_name #ty_generics #where_clause {
+                #[allow(unused_assignments)]
+                fn decode_borrowed(
+                    buf: &#lifetime [u8],
+                    offset: usize,
+                ) -> Result<Self, #crate_name::CodecError> {
+                    #data
+
+                    #( #decode_fields )*
+
+                    Ok(#struct_name {
+                        #( #struct_initialization ),*
+                    })
+                }
+            }
+        }
+    }
+
+    fn generate_impl_shared(&self, sol_mode: bool) -> TokenStream {
+        let struct_name = &self.struct_name;
+        let crate_name = Self::crate_name();
+        let encoder = quote! { #crate_name::Encoder<B, ALIGN, {#sol_mode}> };
+        let decode_shared = quote! { #crate_name::DecodeShared<B, ALIGN, {#sol_mode}> };
+
+        // Fields that can't be shared (e.g. borrowed ones) make the impl not applicable
+        let mut shared_generics = self.encoder_generics();
+        for field in &self.fields {
+            let ty = &field.ty;
+            shared_generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: #decode_shared));
+        }
+        let (impl_generics, _, where_clause) = shared_generics.split_for_impl();
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+
+        // Field offsets mirror `Encoder::decode` of the same mode
+        let decode_fields = self.fields.iter().map(|field| {
+            let ident = &field.ident;
+            let ty = &field.ty;
+            let field_size = if sol_mode {
+                quote! {
+                    if <#ty as #encoder>::IS_DYNAMIC {
+                        32
+                    } else {
+                        #crate_name::align_up::<ALIGN>(<#ty as #encoder>::HEADER_SIZE)
+                    }
+                }
+            } else {
+                quote! {
+                    #crate_name::align_up::<ALIGN>(<#ty as #encoder>::HEADER_SIZE)
+                }
+            };
+            quote! {
+                let #ident = <#ty as #decode_shared>::decode_shared(&tmp, current_offset)?;
+                current_offset += #field_size;
+            }
+        });
+
+        // Solidity structs with dynamic fields are stored at the offset written in their header
+        let data = if sol_mode {
+            quote! {
+                let aligned_offset = #crate_name::align_up::<ALIGN>(offset);
+                let data_offset = if <Self as #encoder>::IS_DYNAMIC {
+                    #crate_name::read_u32_aligned::<B, ALIGN>(buf, aligned_offset)? as usize
+                } else {
+                    aligned_offset
+                };
+                if buf.len() < data_offset {
+                    return Err(#crate_name::CodecError::Decoding(
+                        #crate_name::DecodingError::UnexpectedEof,
+                    ));
+                }
+                let tmp = buf.slice(data_offset..);
+                let mut current_offset = 0;
+            }
+        } else {
+            quote! {
+                let tmp = buf.clone();
+                let mut current_offset = #crate_name::align_up::<ALIGN>(offset);
+            }
+        };
+
+        let struct_initialization = self.fields.iter().map(|field| &field.ident);
+
+        quote! {
+            impl #impl_generics #decode_shared for #struct_name #ty_generics #where_clause {
+                #[allow(unused_assignments)]
+                fn decode_shared(
+                    buf: &::bytes::Bytes,
+                    offset: usize,
+                ) -> Result<Self, #crate_name::CodecError> {
+                    #data
+
+                    #( #decode_fields )*
+
+                    Ok(#struct_name {
+                        #( #struct_initialization ),*
+                    })
+                }
+            }
+        }
+    }
 }
 
 impl ToTokens for CodecStruct {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let sol_mode = self.generate_impl_sol();
         let wasm_mode = self.generate_impl_wasm();
+        let sol_borrowed = self.generate_impl_borrowed(true);
+        let wasm_borrowed = self.generate_impl_borrowed(false);
+        let sol_shared = self.generate_impl_shared(true);
+        let wasm_shared = self.generate_impl_shared(false);
         tokens.extend(quote! {
             #sol_mode
             #wasm_mode
+            #sol_borrowed
+            #wasm_borrowed
+            #sol_shared
+            #wasm_shared
         });
     }
 }
@@ -0,0 +1,266 @@
+//! Integrations with big integer crates other than `alloy-primitives`.
+//!
+//! Every type is converted to its alloy counterpart and shares its layout:
+//! - `primitive_types::{U128, U256}` are `uint128`/`uint256`
+//! - `primitive_types::H160` is `address`, `primitive_types::H256` is `bytes32`
+//! - `num_bigint::BigUint` is `uint256` and `num_bigint::BigInt` is `int256`, values out of range
+//!   return `CodecError::Overflow` instead of being truncated
+//!
+//! `ruint::Uint` is the same type as `alloy_primitives::Uint` (re-exported as
+//! `alloy_primitives::ruint`), so it's supported out of the box.
+use crate::{encoder::Encoder, error::CodecError, packed::PackedEncoder, sink::EncodeSink};
+use byteorder::ByteOrder;
+use bytes::Buf;
+
+/// Implements `Encoder` and `PackedEncoder` for `$type` by converting it into `$via`.
+/// `$into` is fallible, so conversions with a range check can report an overflow.
+#[allow(unused_macros)]
+macro_rules! impl_encoder_via {
+    ($type:ty, $via:ty, $into:expr, $from:expr) => {
+        impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+            Encoder<B, { ALIGN }, { SOL_MODE }> for $type
+        where
+            $via: Encoder<B, { ALIGN }, { SOL_MODE }>,
+        {
+            const HEADER_SIZE: usize = <$via as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+            const IS_DYNAMIC: bool = <$via as Encoder<B, ALIGN, SOL_MODE>>::IS_DYNAMIC;
+
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                let value: $via = $into(self)?;
+                <$via as Encoder<B, ALIGN, SOL_MODE>>::encode(&value, buf, offset)
+            }
+
+            fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+                <$via as Encoder<B, ALIGN, SOL_MODE>>::decode(buf, offset).and_then($from)
+            }
+
+            fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+                <$via as Encoder<B, ALIGN, SOL_MODE>>::partial_decode(buf, offset)
+            }
+        }
+
+        impl PackedEncoder for $type {
+            const PACKED_SIZE: Option<usize> = <$via as PackedEncoder>::PACKED_SIZE;
+            const PACKED_ELEMENT_SIZE: Option<usize> = <$via as PackedEncoder>::PACKED_ELEMENT_SIZE;
+
+            fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+                let value: $via = $into(self)?;
+                value.encode_packed(buf)
+            }
+
+            fn encode_packed_array_element(
+                &self,
+                buf: &mut impl EncodeSink,
+            ) -> Result<(), CodecError> {
+                let value: $via = $into(self)?;
+                value.encode_packed_array_element(buf)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "primitive-types")]
+mod primitive_types_impl {
+    use super::*;
+    use alloy_primitives::{Address, B256, U128, U256};
+    use primitive_types::{H160, H256};
+
+    impl_encoder_via!(
+        primitive_types::U128,
+        U128,
+        |value: &primitive_types::U128| {
+            let mut bytes = [0u8; 16];
+            value.to_big_endian(&mut bytes);
+            Ok::<_, CodecError>(U128::from_be_bytes(bytes))
+        },
+        |value: U128| Ok(primitive_types::U128::from_big_endian(
+            &value.to_be_bytes::<16>()
+        ))
+    );
+
+    impl_encoder_via!(
+        primitive_types::U256,
+        U256,
+        |value: &primitive_types::U256| {
+            let mut bytes = [0u8; 32];
+            value.to_big_endian(&mut bytes);
+            Ok::<_, CodecError>(U256::from_be_bytes(bytes))
+        },
+        |value: U256| Ok(primitive_types::U256::from_big_endian(
+            &value.to_be_bytes::<32>()
+        ))
+    );
+
+    impl_encoder_via!(
+        H160,
+        Address,
+        |value: &H160| Ok::<_, CodecError>(Address::from(value.0)),
+        |value: Address| Ok(H160(value.into_array()))
+    );
+
+    impl_encoder_via!(
+        H256,
+        B256,
+        |value: &H256| Ok::<_, CodecError>(B256::from(value.0)),
+        |value: B256| Ok(H256(value.0))
+    );
+}
+
+#[cfg(feature = "num-bigint")]
+mod num_bigint_impl {
+    use super::*;
+    use alloy_primitives::{I256, U256};
+    use num_bigint::{BigInt, BigUint, Sign};
+
+    impl_encoder_via!(
+        BigUint,
+        U256,
+        |value: &BigUint| U256::try_from_be_slice(&value.to_bytes_be()).ok_or(CodecError::Overflow),
+        |value: U256| Ok(BigUint::from_bytes_be(&value.to_be_bytes::<32>()))
+    );
+
+    impl_encoder_via!(
+        BigInt,
+        I256,
+        |value: &BigInt| {
+            // Minimal two's complement representation, it fits into `int256` only if it's not
+            // longer than 32 bytes
+            let bytes = value.to_signed_bytes_be();
+            if bytes.len() > 32 {
+                return Err(CodecError::Overflow);
+            }
+            let fill = if value.sign() == Sign::Minus {
+                0xFF
+            } else {
+                0x00
+            };
+            let mut word = [fill; 32];
+            word[32 - bytes.len()..].copy_from_slice(&bytes);
+            Ok(I256::from_be_bytes(word))
+        },
+        |value: I256| Ok(BigInt::from_signed_bytes_be(&value.to_be_bytes::<32>()))
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    #[allow(unused_imports)]
+    use crate::encoder::{FluentABI, SolidityABI};
+    use bytes::BytesMut;
+
+    #[cfg(feature = "primitive-types")]
+    #[test]
+    fn test_primitive_types_match_alloy() {
+        use alloy_primitives::{Address, B256, U256};
+        use primitive_types::{H160, H256};
+
+        let value = (
+            primitive_types::U256::from(0x1234_5678u64) << 128,
+            H160::repeat_byte(0xAA),
+            H256::repeat_byte(0xBB),
+        );
+        let alloy_value = (
+            U256::from(0x1234_5678u64) << 128,
+            Address::repeat_byte(0xAA),
+            B256::repeat_byte(0xBB),
+        );
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&value, &mut buf, 0).unwrap();
+        let mut alloy_buf = BytesMut::new();
+        SolidityABI::encode(&alloy_value, &mut alloy_buf, 0).unwrap();
+        assert_eq!(buf, alloy_buf);
+        assert_eq!(
+            SolidityABI::<(primitive_types::U256, H160, H256)>::decode(&buf, 0).unwrap(),
+            value
+        );
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&value, &mut buf, 0).unwrap();
+        let mut alloy_buf = BytesMut::new();
+        FluentABI::encode(&alloy_value, &mut alloy_buf, 0).unwrap();
+        assert_eq!(buf, alloy_buf);
+        assert_eq!(
+            FluentABI::<(primitive_types::U256, H160, H256)>::decode(&buf, 0).unwrap(),
+            value
+        );
+    }
+
+    #[test]
+    fn test_ruint() {
+        use alloy_primitives::ruint;
+
+        let value = ruint::aliases::U256::from(42u64);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&value, &mut buf, 0).unwrap();
+        assert_eq!(
+            SolidityABI::<ruint::aliases::U256>::decode(&buf, 0).unwrap(),
+            value
+        );
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn test_big_uint() {
+        use alloy_sol_types::{sol_data, SolType};
+        use num_bigint::BigUint;
+
+        let value = BigUint::from(u128::MAX) * 3u32;
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&value, &mut buf, 0).unwrap();
+        let expected = alloy_primitives::U256::from(u128::MAX) * alloy_primitives::U256::from(3);
+        assert_eq!(buf.to_vec(), sol_data::Uint::<256>::abi_encode(&expected));
+        assert_eq!(SolidityABI::<BigUint>::decode(&buf, 0).unwrap(), value);
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&value, &mut buf, 0).unwrap();
+        assert_eq!(FluentABI::<BigUint>::decode(&buf, 0).unwrap(), value);
+
+        // 2^256 doesn't fit into uint256
+        let overflow = BigUint::from(1u32) << 256;
+        let mut buf = BytesMut::new();
+        assert!(matches!(
+            SolidityABI::encode(&overflow, &mut buf, 0),
+            Err(CodecError::Overflow)
+        ));
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn test_big_int() {
+        use alloy_primitives::I256;
+        use alloy_sol_types::{sol_data, SolType};
+        use num_bigint::BigInt;
+
+        let min: BigInt = -(BigInt::from(1) << 255usize);
+        let max: BigInt = (BigInt::from(1) << 255usize) - 1;
+
+        for (value, expected) in [
+            (BigInt::from(-1), I256::MINUS_ONE),
+            (BigInt::from(0), I256::ZERO),
+            (min.clone(), I256::MIN),
+            (max.clone(), I256::MAX),
+        ] {
+            let mut buf = BytesMut::new();
+            SolidityABI::encode(&value, &mut buf, 0).unwrap();
+            assert_eq!(buf.to_vec(), sol_data::Int::<256>::abi_encode(&expected));
+            assert_eq!(SolidityABI::<BigInt>::decode(&buf, 0).unwrap(), value);
+
+            let mut buf = BytesMut::new();
+            FluentABI::encode(&value, &mut buf, 0).unwrap();
+            assert_eq!(FluentABI::<BigInt>::decode(&buf, 0).unwrap(), value);
+        }
+
+        for overflow in [min - 1, max + 1] {
+            let mut buf = BytesMut::new();
+            assert!(matches!(
+                SolidityABI::encode(&overflow, &mut buf, 0),
+                Err(CodecError::Overflow)
+            ));
+        }
+    }
+}
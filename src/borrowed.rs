@@ -0,0 +1,836 @@
+use crate::{
+    alloc::{borrow::Cow, format, string::ToString, vec::Vec},
+    bytes::{read_bytes_slice, ByteVec},
+    empty::EmptyVec,
+    encoder::{align_up, check_word_padding, read_u32_aligned, Encoder},
+    error::{CodecError, DecodingError},
+    fixed::{Fixed, UFixed},
+    hash::{ByteOrdered, SolidityMap},
+    primitive::element_header_size,
+    string::invalid_utf8,
+    view::VecView,
+};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+};
+use alloy_primitives::{
+    Address,
+    Bloom,
+    FixedBytes,
+    Function,
+    Log,
+    Signature,
+    Signed,
+    TxKind,
+    Uint,
+};
+use byteorder::ByteOrder;
+use bytes::{Bytes, BytesMut};
+use hashbrown::{HashMap, HashSet};
+
+/// Decoding into values that borrow from the input buffer, e.g. `&[u8]`, `&str`,
+/// `&FixedBytes<N>`, `Cow` or a derived struct with a lifetime. Borrowed data is never copied.
+///
+/// Owned types use the default implementation, that falls back to [`Encoder::decode`], so they can
+/// be mixed with borrowed ones inside vectors, arrays, tuples and derived structs.
+pub trait DecodeBorrowed<'a, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>:
+    Encoder<B, { ALIGN }, { SOL_MODE }>
+{
+    /// Decodes a value from the given buffer starting at the specified offset, borrowing its data
+    /// from the buffer when possible.
+    fn decode_borrowed(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        Self::decode(&buf, offset)
+    }
+}
+
+/// Decoding from a shared `bytes::Bytes` input: decoded `Bytes` payloads are refcounted slices of
+/// the input (see [`Bytes::slice_ref`]) instead of copies, so they share its allocation.
+///
+/// Owned types, including `Vec<u8>` and `ByteVec`, use the default implementation, that falls back
+/// to [`Encoder::decode`].
+pub trait DecodeShared<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>:
+    Encoder<B, { ALIGN }, { SOL_MODE }>
+{
+    /// Decodes a value from the given buffer starting at the specified offset, sharing the buffer
+    /// allocation for byte payloads.
+    fn decode_shared(buf: &Bytes, offset: usize) -> Result<Self, CodecError> {
+        Self::decode(buf, offset)
+    }
+}
+
+/// Returns the part of the buffer starting at `offset`.
+fn tail(buf: &[u8], offset: usize) -> Result<&[u8], CodecError> {
+    buf.get(offset..)
+        .ok_or(CodecError::Decoding(DecodingError::BufferTooSmall {
+            expected: offset,
+            found: buf.len(),
+            msg: "buffer too small to take offset".to_string(),
+        }))
+}
+
+/// Returns the part of the shared buffer starting at `offset`.
+fn shared_tail(buf: &Bytes, offset: usize) -> Result<Bytes, CodecError> {
+    tail(buf, offset).map(|data| buf.slice_ref(data))
+}
+
+/// Returns `size` bytes of the buffer starting at `offset`.
+fn take(buf: &[u8], offset: usize, size: usize) -> Result<&[u8], CodecError> {
+    offset
+        .checked_add(size)
+        .and_then(|end| buf.get(offset..end))
+        .ok_or(CodecError::Decoding(DecodingError::BufferTooSmall {
+            expected: offset.saturating_add(size),
+            found: buf.len(),
+            msg: "buffer too small to decode fixed bytes".to_string(),
+        }))
+}
+
+impl<'a, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for &'a [u8]
+{
+    fn decode_borrowed(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        read_bytes_slice::<B, ALIGN, SOL_MODE>(buf, offset)
+    }
+}
+
+impl<'a, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for &'a str
+{
+    fn decode_borrowed(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        let data = read_bytes_slice::<B, ALIGN, SOL_MODE>(buf, offset)?;
+        core::str::from_utf8(data).map_err(invalid_utf8)
+    }
+}
+
+impl<'a, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for Cow<'a, [u8]>
+{
+    fn decode_borrowed(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        <&[u8] as DecodeBorrowed<B, ALIGN, SOL_MODE>>::decode_borrowed(buf, offset)
+            .map(Cow::Borrowed)
+    }
+}
+
+impl<'a, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for Cow<'a, str>
+{
+    fn decode_borrowed(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        <&str as DecodeBorrowed<B, ALIGN, SOL_MODE>>::decode_borrowed(buf, offset)
+            .map(Cow::Borrowed)
+    }
+}
+
+/// Same layout as `FixedBytes<N>`: the padding of a Solidity word must be zeroed.
+impl<'a, const N: usize, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for &'a FixedBytes<N>
+where
+    FixedBytes<N>: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    fn decode_borrowed(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        let data = if SOL_MODE {
+            let header_size = <FixedBytes<N> as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+            let word = take(buf, align_up::<32>(offset), header_size)?;
+            check_word_padding(word, 0, N, 0x00)?;
+            &word[..N]
+        } else {
+            take(buf, offset, N)?
+        };
+
+        Ok(<&FixedBytes<N>>::try_from(data)
+            .unwrap_or_else(|_| unreachable!("exactly N bytes are taken")))
+    }
+}
+
+/// Same layout as `Address`: in Solidity mode the address is right-aligned in the word.
+impl<'a, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for &'a Address
+where
+    Address: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    fn decode_borrowed(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        let size = Address::len_bytes();
+        let data = if SOL_MODE {
+            &take(buf, offset, 32)?[32 - size..]
+        } else {
+            take(buf, offset, size)?
+        };
+
+        Ok(<&Address>::try_from(data)
+            .unwrap_or_else(|_| unreachable!("exactly 20 bytes are taken")))
+    }
+}
+
+impl<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for Vec<T>
+where
+    T: DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }>,
+    Vec<T>: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    fn decode_borrowed(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        VecView::<T, B, ALIGN, SOL_MODE>::new(buf, offset)?
+            .iter_borrowed()
+            .collect()
+    }
+}
+
+impl<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool, const N: usize>
+    DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for [T; N]
+where
+    T: DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }>,
+{
+    fn decode_borrowed(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        let item_size = element_header_size::<T, B, ALIGN, SOL_MODE>();
+
+        let (data, offset) = if SOL_MODE && T::IS_DYNAMIC {
+            let data_offset = read_u32_aligned::<B, ALIGN>(&buf, offset)? as usize;
+            (tail(buf, data_offset)?, 0)
+        } else {
+            (buf, offset)
+        };
+
+        let total_size = offset + (item_size * N);
+        if data.len() < total_size {
+            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+                expected: total_size,
+                found: data.len(),
+                msg: "buf too small to decode [T; N]".to_string(),
+            }));
+        }
+
+        let items = (0..N)
+            .map(|i| T::decode_borrowed(data, offset + (item_size * i)))
+            .collect::<Result<Vec<T>, CodecError>>()?;
+
+        Ok(items
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("exactly N items are decoded")))
+    }
+}
+
+impl<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for Option<T>
+where
+    T: DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }>,
+    Option<T>: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    fn decode_borrowed(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        let flag_size = align_up::<ALIGN>(<bool as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
+
+        let (data, offset) = if SOL_MODE && T::IS_DYNAMIC {
+            let data_offset = read_u32_aligned::<B, ALIGN>(&buf, offset)? as usize;
+            (tail(buf, data_offset)?, 0)
+        } else {
+            (buf, offset)
+        };
+
+        if <bool as Encoder<B, ALIGN, SOL_MODE>>::decode(&data, offset)? {
+            Ok(Some(T::decode_borrowed(data, offset + flag_size)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<'a, T, E, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for Result<T, E>
+where
+    T: DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }>,
+    E: DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }>,
+    Result<T, E>: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    fn decode_borrowed(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        let tag_size = align_up::<ALIGN>(<u8 as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
+
+        let (data, offset) = if SOL_MODE && <Self as Encoder<B, ALIGN, SOL_MODE>>::IS_DYNAMIC {
+            let data_offset = read_u32_aligned::<B, ALIGN>(&buf, offset)? as usize;
+            (tail(buf, data_offset)?, 0)
+        } else {
+            (buf, offset)
+        };
+
+        match <u8 as Encoder<B, ALIGN, SOL_MODE>>::decode(&data, offset)? {
+            0 => Ok(Ok(T::decode_borrowed(data, offset + tag_size)?)),
+            1 => Ok(Err(E::decode_borrowed(data, offset + tag_size)?)),
+            tag => Err(invalid_result_tag(tag)),
+        }
+    }
+}
+
+fn invalid_result_tag(tag: u8) -> CodecError {
+    CodecError::Decoding(DecodingError::InvalidData(format!(
+        "invalid Result tag: {}",
+        tag
+    )))
+}
+
+/// Mirrors the tuple decoding: a dynamic tuple is read from the offset stored in its header.
+macro_rules! impl_decode_borrowed_for_tuple {
+    ($($T:ident),+) => {
+        impl<'a, $($T,)+ B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+            DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for ($($T,)+)
+        where
+            $($T: DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }>,)+
+            ($($T,)+): Encoder<B, { ALIGN }, { SOL_MODE }>,
+        {
+            #[allow(unused_assignments)]
+            fn decode_borrowed(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+                let data = if <Self as Encoder<B, ALIGN, SOL_MODE>>::IS_DYNAMIC {
+                    let data_offset = read_u32_aligned::<B, ALIGN>(&buf, offset)? as usize;
+                    tail(buf, data_offset)?
+                } else {
+                    tail(buf, offset)?
+                };
+
+                let mut current_offset = 0;
+
+                Ok(($(
+                    {
+                        let value = $T::decode_borrowed(data, current_offset)?;
+                        current_offset += element_header_size::<$T, B, ALIGN, SOL_MODE>();
+                        value
+                    },
+                )+))
+            }
+        }
+    };
+}
+
+impl_decode_borrowed_for_tuple!(T1);
+impl_decode_borrowed_for_tuple!(T1, T2);
+impl_decode_borrowed_for_tuple!(T1, T2, T3);
+impl_decode_borrowed_for_tuple!(T1, T2, T3, T4);
+impl_decode_borrowed_for_tuple!(T1, T2, T3, T4, T5);
+impl_decode_borrowed_for_tuple!(T1, T2, T3, T4, T5, T6);
+impl_decode_borrowed_for_tuple!(T1, T2, T3, T4, T5, T6, T7);
+impl_decode_borrowed_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8);
+
+impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeShared<B, { ALIGN }, { SOL_MODE }> for Bytes
+{
+    fn decode_shared(buf: &Bytes, offset: usize) -> Result<Self, CodecError> {
+        read_bytes_slice::<B, ALIGN, SOL_MODE>(buf, offset).map(|data| buf.slice_ref(data))
+    }
+}
+
+impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeShared<B, { ALIGN }, { SOL_MODE }> for alloy_primitives::Bytes
+where
+    alloy_primitives::Bytes: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    fn decode_shared(buf: &Bytes, offset: usize) -> Result<Self, CodecError> {
+        <Bytes as DecodeShared<B, ALIGN, SOL_MODE>>::decode_shared(buf, offset).map(Self::from)
+    }
+}
+
+impl<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeShared<B, { ALIGN }, { SOL_MODE }> for Vec<T>
+where
+    T: DecodeShared<B, { ALIGN }, { SOL_MODE }>,
+    Vec<T>: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    fn decode_shared(buf: &Bytes, offset: usize) -> Result<Self, CodecError> {
+        let view = VecView::<T, B, ALIGN, SOL_MODE>::new(buf, offset)?;
+        let data = buf.slice_ref(view.data());
+        let item_size = element_header_size::<T, B, ALIGN, SOL_MODE>();
+
+        (0..view.len())
+            .map(|i| T::decode_shared(&data, item_size * i))
+            .collect()
+    }
+}
+
+impl<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool, const N: usize>
+    DecodeShared<B, { ALIGN }, { SOL_MODE }> for [T; N]
+where
+    T: DecodeShared<B, { ALIGN }, { SOL_MODE }>,
+{
+    fn decode_shared(buf: &Bytes, offset: usize) -> Result<Self, CodecError> {
+        let item_size = element_header_size::<T, B, ALIGN, SOL_MODE>();
+
+        let (data, offset) = if SOL_MODE && T::IS_DYNAMIC {
+            let data_offset = read_u32_aligned::<B, ALIGN>(buf, offset)? as usize;
+            (shared_tail(buf, data_offset)?, 0)
+        } else {
+            (buf.clone(), offset)
+        };
+
+        let total_size = offset + (item_size * N);
+        if data.len() < total_size {
+            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+                expected: total_size,
+                found: data.len(),
+                msg: "buf too small to decode [T; N]".to_string(),
+            }));
+        }
+
+        let items = (0..N)
+            .map(|i| T::decode_shared(&data, offset + (item_size * i)))
+            .collect::<Result<Vec<T>, CodecError>>()?;
+
+        Ok(items
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("exactly N items are decoded")))
+    }
+}
+
+impl<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeShared<B, { ALIGN }, { SOL_MODE }> for Option<T>
+where
+    T: DecodeShared<B, { ALIGN }, { SOL_MODE }>,
+    Option<T>: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    fn decode_shared(buf: &Bytes, offset: usize) -> Result<Self, CodecError> {
+        let flag_size = align_up::<ALIGN>(<bool as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
+
+        let (data, offset) = if SOL_MODE && T::IS_DYNAMIC {
+            let data_offset = read_u32_aligned::<B, ALIGN>(buf, offset)? as usize;
+            (shared_tail(buf, data_offset)?, 0)
+        } else {
+            (buf.clone(), offset)
+        };
+
+        if <bool as Encoder<B, ALIGN, SOL_MODE>>::decode(&data, offset)? {
+            Ok(Some(T::decode_shared(&data, offset + flag_size)?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T, E, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeShared<B, { ALIGN }, { SOL_MODE }> for Result<T, E>
+where
+    T: DecodeShared<B, { ALIGN }, { SOL_MODE }>,
+    E: DecodeShared<B, { ALIGN }, { SOL_MODE }>,
+    Result<T, E>: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    fn decode_shared(buf: &Bytes, offset: usize) -> Result<Self, CodecError> {
+        let tag_size = align_up::<ALIGN>(<u8 as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
+
+        let (data, offset) = if SOL_MODE && <Self as Encoder<B, ALIGN, SOL_MODE>>::IS_DYNAMIC {
+            let data_offset = read_u32_aligned::<B, ALIGN>(buf, offset)? as usize;
+            (shared_tail(buf, data_offset)?, 0)
+        } else {
+            (buf.clone(), offset)
+        };
+
+        match <u8 as Encoder<B, ALIGN, SOL_MODE>>::decode(&data, offset)? {
+            0 => Ok(Ok(T::decode_shared(&data, offset + tag_size)?)),
+            1 => Ok(Err(E::decode_shared(&data, offset + tag_size)?)),
+            tag => Err(invalid_result_tag(tag)),
+        }
+    }
+}
+
+macro_rules! impl_decode_shared_for_tuple {
+    ($($T:ident),+) => {
+        impl<$($T,)+ B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+            DecodeShared<B, { ALIGN }, { SOL_MODE }> for ($($T,)+)
+        where
+            $($T: DecodeShared<B, { ALIGN }, { SOL_MODE }>,)+
+            ($($T,)+): Encoder<B, { ALIGN }, { SOL_MODE }>,
+        {
+            #[allow(unused_assignments)]
+            fn decode_shared(buf: &Bytes, offset: usize) -> Result<Self, CodecError> {
+                let data = if <Self as Encoder<B, ALIGN, SOL_MODE>>::IS_DYNAMIC {
+                    let data_offset = read_u32_aligned::<B, ALIGN>(buf, offset)? as usize;
+                    shared_tail(buf, data_offset)?
+                } else {
+                    shared_tail(buf, offset)?
+                };
+
+                let mut current_offset = 0;
+
+                Ok(($(
+                    {
+                        let value = $T::decode_shared(&data, current_offset)?;
+                        current_offset += element_header_size::<$T, B, ALIGN, SOL_MODE>();
+                        value
+                    },
+                )+))
+            }
+        }
+    };
+}
+
+impl_decode_shared_for_tuple!(T1);
+impl_decode_shared_for_tuple!(T1, T2);
+impl_decode_shared_for_tuple!(T1, T2, T3);
+impl_decode_shared_for_tuple!(T1, T2, T3, T4);
+impl_decode_shared_for_tuple!(T1, T2, T3, T4, T5);
+impl_decode_shared_for_tuple!(T1, T2, T3, T4, T5, T6);
+impl_decode_shared_for_tuple!(T1, T2, T3, T4, T5, T6, T7);
+impl_decode_shared_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8);
+
+/// Owned types don't borrow or share anything, they're decoded with [`Encoder::decode`].
+macro_rules! impl_decode_owned {
+    ($([$($generics:tt)*] $type:ty),+ $(,)?) => {
+        $(
+            impl<'a, $($generics)* B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+                DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for $type
+            where
+                $type: Encoder<B, { ALIGN }, { SOL_MODE }>,
+            {
+            }
+
+            impl<$($generics)* B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+                DecodeShared<B, { ALIGN }, { SOL_MODE }> for $type
+            where
+                $type: Encoder<B, { ALIGN }, { SOL_MODE }>,
+            {
+            }
+        )+
+    };
+}
+
+// Shared byte payloads are still copied when borrowing is requested
+impl<'a, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for Bytes
+{
+}
+
+impl<'a, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }> for alloy_primitives::Bytes
+where
+    alloy_primitives::Bytes: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+}
+
+impl_decode_owned!(
+    [] (),
+    [] bool,
+    [] u8,
+    [] u16,
+    [] u32,
+    [] u64,
+    [] u128,
+    [] i8,
+    [] i16,
+    [] i32,
+    [] i64,
+    [] i128,
+    [] String,
+    [] BytesMut,
+    [] ByteVec,
+    [] EmptyVec,
+    [] Address,
+    [] Function,
+    [] Bloom,
+    [] Signature,
+    [] Log,
+    [] TxKind,
+    [const N: usize,] FixedBytes<N>,
+    [const BITS: usize, const LIMBS: usize,] Uint<BITS, LIMBS>,
+    [const BITS: usize, const LIMBS: usize,] Signed<BITS, LIMBS>,
+    [const M: usize, const N: usize,] Fixed<M, N>,
+    [const M: usize, const N: usize,] UFixed<M, N>,
+    [K, V, S,] HashMap<K, V, S>,
+    [T, S,] HashSet<T, S>,
+    [K, V,] BTreeMap<K, V>,
+    [T,] BTreeSet<T>,
+    [K, V,] SolidityMap<K, V>,
+    [T,] ByteOrdered<T>,
+);
+
+#[cfg(feature = "std")]
+impl_decode_owned!(
+    [K, V, S,] std::collections::HashMap<K, V, S>,
+    [T, S,] std::collections::HashSet<T, S>,
+);
+
+#[cfg(feature = "indexmap")]
+impl_decode_owned!(
+    [K, V, S,] indexmap::IndexMap<K, V, S>,
+    [T, S,] indexmap::IndexSet<T, S>,
+);
+
+#[cfg(feature = "primitive-types")]
+impl_decode_owned!(
+    [] primitive_types::U128,
+    [] primitive_types::U256,
+    [] primitive_types::H160,
+    [] primitive_types::H256,
+);
+
+#[cfg(feature = "num-bigint")]
+impl_decode_owned!([] num_bigint::BigUint, [] num_bigint::BigInt);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::{FluentABI, SolidityABI};
+    use alloy_primitives::{Address, B256, U256};
+
+    fn borrows_from(buf: &[u8], data: &[u8]) -> bool {
+        buf.as_ptr_range().contains(&data.as_ptr())
+    }
+
+    #[test]
+    fn test_decode_borrowed_strings() {
+        let original = vec!["hello", "", "borrowed world"];
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let decoded = SolidityABI::<Vec<&str>>::decode_borrowed(&buf, 0).unwrap();
+        assert_eq!(decoded, original);
+        assert!(borrows_from(&buf, decoded[2].as_bytes()));
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        let decoded = FluentABI::<Vec<&str>>::decode_borrowed(&buf, 0).unwrap();
+        assert_eq!(decoded, original);
+        assert!(borrows_from(&buf, decoded[0].as_bytes()));
+    }
+
+    #[test]
+    fn test_decode_borrowed_mixed_tuple() {
+        let hash = B256::repeat_byte(0xaa);
+        let address = Address::repeat_byte(0xbb);
+        let original = (hash, address, U256::from(7), Bytes::from_static(b"payload"));
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let (decoded_hash, decoded_address, value, data) =
+            SolidityABI::<(&B256, &Address, U256, &[u8])>::decode_borrowed(&buf, 0).unwrap();
+        assert_eq!(
+            (*decoded_hash, *decoded_address, value, data),
+            (hash, address, U256::from(7), &b"payload"[..])
+        );
+        assert!(borrows_from(&buf, decoded_hash.as_slice()));
+        assert!(borrows_from(&buf, decoded_address.as_slice()));
+        assert!(borrows_from(&buf, data));
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        let (decoded_hash, decoded_address, value, data) =
+            FluentABI::<(&B256, &Address, U256, &[u8])>::decode_borrowed(&buf, 0).unwrap();
+        assert_eq!(
+            (*decoded_hash, *decoded_address, value, data),
+            (hash, address, U256::from(7), &b"payload"[..])
+        );
+        assert!(borrows_from(&buf, decoded_address.as_slice()));
+        assert!(borrows_from(&buf, data));
+    }
+
+    #[test]
+    fn test_decode_borrowed_cow_and_arrays() {
+        let original: [Cow<str>; 2] = [Cow::Borrowed("first"), Cow::Owned("second".into())];
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let decoded = SolidityABI::<[Cow<str>; 2]>::decode_borrowed(&buf, 0).unwrap();
+        assert_eq!(decoded, original);
+        assert!(decoded.iter().all(|s| matches!(s, Cow::Borrowed(_))));
+
+        // Regular decoding of a `Cow` produces an owned value
+        let decoded = SolidityABI::<[Cow<str>; 2]>::decode(&buf, 0).unwrap();
+        assert!(decoded.iter().all(|s| matches!(s, Cow::Owned(_))));
+
+        let original: Cow<[u8]> = Cow::Borrowed(&[1, 2, 3]);
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        let decoded = FluentABI::<Cow<[u8]>>::decode_borrowed(&buf, 0).unwrap();
+        assert_eq!(decoded, original);
+        assert!(matches!(decoded, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_decode_borrowed_option_and_result() {
+        type Value<'a> = (Option<&'a str>, Result<&'a [u8], u32>, Option<Bytes>);
+        let original: Value = (Some("optional"), Ok(&[1, 2, 3]), None);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let decoded = SolidityABI::<Value>::decode_borrowed(&buf, 0).unwrap();
+        assert_eq!(decoded, original);
+        assert!(borrows_from(&buf, decoded.0.unwrap().as_bytes()));
+    }
+
+    #[test]
+    fn test_decode_shared_option_and_result() {
+        let original: Result<Option<Bytes>, Bytes> = Ok(Some(Bytes::from_static(b"shared")));
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        let input = buf.freeze();
+        let decoded = FluentABI::<Result<Option<Bytes>, Bytes>>::decode_shared(&input, 0).unwrap();
+        assert_eq!(decoded, original);
+        assert!(borrows_from(
+            &input,
+            decoded.as_ref().unwrap().as_ref().unwrap()
+        ));
+    }
+
+    #[test]
+    fn test_decode_borrowed_errors() {
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&Bytes::from_static(&[0xff, 0xfe]), &mut buf, 0).unwrap();
+        assert!(matches!(
+            SolidityABI::<&str>::decode_borrowed(&buf, 0),
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+
+        // The data is cut off
+        assert!(SolidityABI::<&[u8]>::decode_borrowed(&buf[..64], 0).is_err());
+
+        // Non-zero padding of `bytes4`
+        let mut word = [0u8; 32];
+        word[31] = 1;
+        assert!(SolidityABI::<&FixedBytes<4>>::decode_borrowed(&word, 0).is_err());
+
+        // Plain `decode` can't produce borrowed values
+        assert!(SolidityABI::<&[u8]>::decode(&buf, 0).is_err());
+        assert!(SolidityABI::<&B256>::decode(&&word[..], 0).is_err());
+    }
+
+    #[test]
+    fn test_decode_shared_bytes() {
+        let payloads = vec![
+            Bytes::from_static(b"first payload"),
+            Bytes::new(),
+            Bytes::from(vec![0xab; 100]),
+        ];
+        let original = (
+            7u32,
+            alloy_primitives::Bytes::from_static(b"calldata"),
+            payloads,
+        );
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let input = buf.freeze();
+        let decoded =
+            SolidityABI::<(u32, alloy_primitives::Bytes, Vec<Bytes>)>::decode_shared(&input, 0)
+                .unwrap();
+        assert_eq!(decoded, original);
+        assert!(borrows_from(&input, &decoded.1));
+        assert!(borrows_from(&input, &decoded.2[0]));
+        assert!(borrows_from(&input, &decoded.2[2]));
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original.2, &mut buf, 0).unwrap();
+        let input = buf.freeze();
+        let decoded = FluentABI::<Vec<Bytes>>::decode_shared(&input, 0).unwrap();
+        assert_eq!(decoded, original.2);
+        assert!(borrows_from(&input, &decoded[0]));
+        assert!(borrows_from(&input, &decoded[2]));
+
+        // Regular decoding copies the payloads
+        let decoded = FluentABI::<Vec<Bytes>>::decode(&input, 0).unwrap();
+        assert!(!borrows_from(&input, &decoded[0]));
+    }
+
+    #[cfg(feature = "derive")]
+    mod derived {
+        use super::*;
+        use crate::{align_up, read_u32_aligned, write_u32_aligned, Codec};
+        use bytes::Buf;
+
+        #[derive(Codec, Debug, PartialEq)]
+        struct Transfer<'a> {
+            from: &'a Address,
+            amount: U256,
+            memo: &'a str,
+            data: Cow<'a, [u8]>,
+        }
+
+        fn transfer(from: &Address) -> Transfer<'_> {
+            Transfer {
+                from,
+                amount: U256::from(1000),
+                memo: "zero-copy",
+                data: Cow::Borrowed(&[0xde, 0xad, 0xbe, 0xef]),
+            }
+        }
+
+        #[test]
+        fn test_derived_struct_with_lifetime() {
+            let from = Address::repeat_byte(0x11);
+            let original = transfer(&from);
+
+            let mut buf = BytesMut::new();
+            SolidityABI::encode(&original, &mut buf, 0).unwrap();
+            let decoded = SolidityABI::<Transfer>::decode_borrowed(&buf, 0).unwrap();
+            assert_eq!(decoded, original);
+            assert!(borrows_from(&buf, decoded.from.as_slice()));
+            assert!(borrows_from(&buf, decoded.memo.as_bytes()));
+            assert!(matches!(decoded.data, Cow::Borrowed(_)));
+
+            let mut buf = BytesMut::new();
+            FluentABI::encode(&original, &mut buf, 0).unwrap();
+            let decoded = FluentABI::<Transfer>::decode_borrowed(&buf, 0).unwrap();
+            assert_eq!(decoded, original);
+            assert!(borrows_from(&buf, decoded.memo.as_bytes()));
+        }
+
+        #[derive(Codec, Debug, PartialEq)]
+        struct Forward {
+            target: Address,
+            value: U256,
+            calldata: alloy_primitives::Bytes,
+        }
+
+        #[test]
+        fn test_derived_struct_shared() {
+            let original = Forward {
+                target: Address::repeat_byte(0x33),
+                value: U256::from(5),
+                calldata: alloy_primitives::Bytes::from(vec![0x42; 68]),
+            };
+
+            let mut buf = BytesMut::new();
+            SolidityABI::encode(&original, &mut buf, 0).unwrap();
+            let input = buf.freeze();
+            let decoded = SolidityABI::<Forward>::decode_shared(&input, 0).unwrap();
+            assert_eq!(decoded, original);
+            assert!(borrows_from(&input, &decoded.calldata));
+
+            let mut buf = BytesMut::new();
+            FluentABI::encode(&original, &mut buf, 0).unwrap();
+            let input = buf.freeze();
+            let decoded = FluentABI::<Forward>::decode_shared(&input, 0).unwrap();
+            assert_eq!(decoded, original);
+            assert!(borrows_from(&input, &decoded.calldata));
+        }
+
+        #[test]
+        fn test_derived_struct_vec_shared() {
+            let original: Vec<Forward> = (1..=3u8)
+                .map(|i| Forward {
+                    target: Address::repeat_byte(i),
+                    value: U256::from(i),
+                    calldata: alloy_primitives::Bytes::from(vec![i; 40 * i as usize]),
+                })
+                .collect();
+
+            let mut buf = BytesMut::new();
+            SolidityABI::encode(&original, &mut buf, 0).unwrap();
+            let input = buf.freeze();
+            let decoded = SolidityABI::<Vec<Forward>>::decode_shared(&input, 0).unwrap();
+            assert_eq!(decoded, original);
+            assert!(borrows_from(&input, &decoded[2].calldata));
+
+            let mut buf = BytesMut::new();
+            FluentABI::encode(&original, &mut buf, 0).unwrap();
+            let input = buf.freeze();
+            let decoded = FluentABI::<Vec<Forward>>::decode_shared(&input, 0).unwrap();
+            assert_eq!(decoded, original);
+        }
+
+        #[test]
+        fn test_derived_struct_in_array() {
+            let from = Address::repeat_byte(0x22);
+            let original = [transfer(&from), transfer(&from)];
+
+            let mut buf = BytesMut::new();
+            SolidityABI::encode(&original, &mut buf, 0).unwrap();
+            let decoded = SolidityABI::<[Transfer; 2]>::decode_borrowed(&buf, 0).unwrap();
+            assert_eq!(decoded, original);
+
+            let mut buf = BytesMut::new();
+            FluentABI::encode(&original, &mut buf, 0).unwrap();
+            let decoded = FluentABI::<[Transfer; 2]>::decode_borrowed(&buf, 0).unwrap();
+            assert_eq!(decoded, original);
+        }
+    }
+}
@@ -1,11 +1,23 @@
 use crate::{
-    alloc::string::ToString,
-    encoder::{align_up, read_u32_aligned, write_u32_aligned},
+    alloc::{borrow::Cow, string::ToString, vec::Vec},
+    encoder::{
+        align_up,
+        contiguous,
+        ensure_buf_size,
+        read_u32_aligned,
+        write_u32_aligned,
+        Encoder,
+    },
     error::{CodecError, DecodingError},
+    packed::{write_packed_right_padded, PackedEncoder},
+    sink::EncodeSink,
 };
 use byteorder::ByteOrder;
 use bytes::{Buf, Bytes, BytesMut};
-use core::mem;
+use core::{
+    mem,
+    ops::{Deref, DerefMut},
+};
 
 /// Universal function to write bytes in Solidity or WASM compatible format
 ///
@@ -36,14 +48,14 @@ use core::mem;
 /// let data = &[1, 2, 3, 4, 5];
 /// let elements = data.len() as u32;
 /// let written = write_bytes::<BigEndian, 32, true>(&mut buf, 0, data, elements);
-/// assert_eq!(written, 37);
+/// assert_eq!(written.unwrap(), 37);
 /// ```
 pub fn write_bytes<B, const ALIGN: usize, const SOL_MODE: bool>(
-    buf: &mut BytesMut,
+    buf: &mut impl EncodeSink,
     offset: usize,
     data: &[u8],
     elements: u32, // number of elements in a dynamic array
-) -> usize
+) -> Result<usize, CodecError>
 where
     B: ByteOrder,
 {
@@ -56,68 +68,132 @@ where
 
 /// Write bytes in Solidity compatible format
 pub fn write_bytes_solidity<B: ByteOrder, const ALIGN: usize>(
-    buf: &mut BytesMut,
+    buf: &mut impl EncodeSink,
     offset: usize,
     data: &[u8],
     elements: u32, // Number of elements
-) -> usize {
+) -> Result<usize, CodecError> {
     // Ensure we have enough space to write the offset
 
-    if buf.len() < offset {
-        buf.resize(offset, 0);
-    }
+    ensure_buf_size(buf, offset)?;
     let data_offset = buf.len();
 
     // Write length of the data (number of elements)
-    write_u32_aligned::<B, ALIGN>(buf, data_offset, elements as u32);
+    write_u32_aligned::<B, ALIGN>(buf, data_offset, elements)?;
 
     // Append the actual data
-    buf.extend_from_slice(data);
+    buf.append(data)?;
 
     // Return the number of bytes written (including alignment)
-    buf.len() - data_offset
+    Ok(buf.len() - data_offset)
 }
 
 /// Write bytes in WASM compatible format
 pub fn write_bytes_wasm<B: ByteOrder, const ALIGN: usize>(
-    buf: &mut BytesMut,
+    buf: &mut impl EncodeSink,
     offset: usize,
     data: &[u8],
-) -> usize {
+) -> Result<usize, CodecError> {
     let aligned_elem_size = align_up::<ALIGN>(mem::size_of::<u32>());
     let aligned_header_size = aligned_elem_size * 2;
 
     // Ensure we have enough space to write the header
-    if buf.len() < offset + aligned_header_size {
-        buf.resize(offset + aligned_header_size, 0);
-    }
+    ensure_buf_size(buf, offset + aligned_header_size)?;
 
     // We append the data to the end of buffer
     let data_offset = buf.len();
 
     // Write offset and data size
-    write_u32_aligned::<B, ALIGN>(buf, offset, data_offset as u32);
-    write_u32_aligned::<B, ALIGN>(buf, offset + aligned_elem_size, data.len() as u32);
+    write_u32_aligned::<B, ALIGN>(buf, offset, data_offset as u32)?;
+    write_u32_aligned::<B, ALIGN>(buf, offset + aligned_elem_size, data.len() as u32)?;
 
     // Append the actual data
-    buf.extend_from_slice(data);
+    buf.append(data)?;
+
+    Ok(buf.len() - data_offset)
+}
+
+/// Encodes a byte slice as a dynamic `bytes` value:
+/// - Solidity mode: offset in the header, length and data in the tail
+/// - Fluent mode: offset and length in the header, data in the tail
+///
+/// The buffer is padded to `ALIGN` after the data.
+pub fn encode_bytes<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    data: &[u8],
+    buf: &mut impl EncodeSink,
+    offset: usize,
+) -> Result<(), CodecError> {
+    let aligned_header_size = if SOL_MODE {
+        align_up::<32>(32)
+    } else {
+        align_up::<ALIGN>(4)
+    };
+
+    // Ensure the buffer has enough space for the header
+    if buf.len() < offset + aligned_header_size {
+        buf.resize_zeroed(offset + aligned_header_size)?;
+    }
+
+    if SOL_MODE {
+        // Write the offset of the data (current length of the buffer)
+        write_u32_aligned::<B, ALIGN>(buf, offset, buf.len() as u32)?;
+        write_bytes::<B, ALIGN, true>(buf, buf.len(), data, data.len() as u32)?;
+    } else {
+        write_bytes::<B, ALIGN, false>(buf, offset, data, data.len() as u32)?;
+    }
+
+    // Add padding if necessary to ensure the buffer remains aligned
+    if buf.len() % ALIGN != 0 {
+        let padding = ALIGN - (buf.len() % ALIGN);
+        buf.resize_zeroed(buf.len() + padding)?;
+    }
+
+    Ok(())
+}
 
-    buf.len() - data_offset
+/// Returns the number of bytes [`encode_bytes`] writes for `len` bytes of data: the header, the
+/// length word in Solidity mode and the data padded to `ALIGN`.
+pub(crate) const fn bytes_encoded_len<const ALIGN: usize, const SOL_MODE: bool>(
+    len: usize,
+) -> usize {
+    let aligned_elem_size = align_up::<ALIGN>(4);
+    if SOL_MODE {
+        32 + aligned_elem_size + align_up::<ALIGN>(len)
+    } else {
+        aligned_elem_size * 2 + align_up::<ALIGN>(len)
+    }
 }
 
 pub fn read_bytes<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
     buf: &impl Buf,
     offset: usize,
 ) -> Result<Bytes, CodecError> {
-    let (data_offset, data_len) = read_bytes_header::<B, ALIGN, SOL_MODE>(buf, offset)?;
+    let chunk = contiguous(buf)?;
+    read_bytes_slice::<B, ALIGN, SOL_MODE>(&chunk, offset).map(Bytes::copy_from_slice)
+}
+
+/// Returns the bytes data as a slice of the input buffer, without copying it.
+pub fn read_bytes_slice<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &[u8],
+    offset: usize,
+) -> Result<&[u8], CodecError> {
+    let (data_offset, data_len) = read_bytes_header::<B, ALIGN, SOL_MODE>(&buf, offset)?;
 
-    let data = if SOL_MODE {
-        buf.chunk()[data_offset + 32..data_offset + 32 + data_len].to_vec()
+    let data_start = if SOL_MODE {
+        data_offset.checked_add(32)
     } else {
-        buf.chunk()[data_offset..data_offset + data_len].to_vec()
+        Some(data_offset)
     };
-
-    Ok(Bytes::from(data))
+    let data_end = data_start.and_then(|start| start.checked_add(data_len));
+
+    match (data_start, data_end) {
+        (Some(start), Some(end)) if end <= buf.len() => Ok(&buf[start..end]),
+        _ => Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+            expected: data_end.unwrap_or(usize::MAX),
+            found: buf.len(),
+            msg: "buffer too small to read bytes data".to_string(),
+        })),
+    }
 }
 
 /// Reads the header of the bytes data in Solidity or WASM compatible format
@@ -199,6 +275,193 @@ pub fn read_bytes_header_solidity<B: ByteOrder, const ALIGN: usize>(
     Ok((data_offset, data_len))
 }
 
+/// A byte vector that is encoded as Solidity `bytes`.
+///
+/// `Vec<u8>` goes through the generic `Vec<T>` implementation and is encoded as `uint8[]`, with
+/// every element taking a full word. Wrap it into `ByteVec` to get the compact `bytes` encoding.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteVec(pub Vec<u8>);
+
+impl ByteVec {
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl Deref for ByteVec {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ByteVec {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl AsRef<[u8]> for ByteVec {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for ByteVec {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&[u8]> for ByteVec {
+    fn from(value: &[u8]) -> Self {
+        Self(value.to_vec())
+    }
+}
+
+impl From<Bytes> for ByteVec {
+    fn from(value: Bytes) -> Self {
+        Self(value.into())
+    }
+}
+
+impl From<ByteVec> for Vec<u8> {
+    fn from(value: ByteVec) -> Self {
+        value.0
+    }
+}
+
+/// Implements `Encoder` and `PackedEncoder` for owned byte containers, all of them share the
+/// `bytes` layout and are decoded through `read_bytes`.
+macro_rules! impl_bytes_like {
+    ($type:ty) => {
+        impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+            Encoder<B, { ALIGN }, { SOL_MODE }> for $type
+        {
+            const HEADER_SIZE: usize = if SOL_MODE { 32 } else { size_of::<u32>() * 2 };
+            const IS_DYNAMIC: bool = true;
+
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                encode_bytes::<B, ALIGN, SOL_MODE>(self, buf, offset)
+            }
+
+            fn encoded_len(&self) -> usize {
+                bytes_encoded_len::<ALIGN, SOL_MODE>(self.len())
+            }
+
+            fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+                Ok(Self::from(read_bytes::<B, ALIGN, SOL_MODE>(buf, offset)?))
+            }
+
+            fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+                read_bytes_header::<B, ALIGN, SOL_MODE>(buf, offset)
+            }
+        }
+
+        impl PackedEncoder for $type {
+            const PACKED_SIZE: Option<usize> = None;
+
+            fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+                buf.append(self)
+            }
+
+            fn encode_packed_array_element(
+                &self,
+                buf: &mut impl EncodeSink,
+            ) -> Result<(), CodecError> {
+                write_packed_right_padded(buf, self)
+            }
+        }
+    };
+}
+
+impl_bytes_like!(Bytes);
+impl_bytes_like!(BytesMut);
+impl_bytes_like!(ByteVec);
+
+/// Borrowed byte slices can only be encoded, decode into `Bytes` or `ByteVec` instead.
+impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, { ALIGN }, { SOL_MODE }>
+    for &[u8]
+{
+    const HEADER_SIZE: usize = <Bytes as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+    const IS_DYNAMIC: bool = true;
+
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        encode_bytes::<B, ALIGN, SOL_MODE>(self, buf, offset)
+    }
+
+    fn encoded_len(&self) -> usize {
+        bytes_encoded_len::<ALIGN, SOL_MODE>(self.len())
+    }
+
+    fn decode(_buf: &impl Buf, _offset: usize) -> Result<Self, CodecError> {
+        Err(CodecError::Decoding(DecodingError::InvalidData(
+            "can't decode into a borrowed &[u8], use decode_borrowed or decode into Bytes instead"
+                .to_string(),
+        )))
+    }
+
+    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        read_bytes_header::<B, ALIGN, SOL_MODE>(buf, offset)
+    }
+}
+
+impl PackedEncoder for &[u8] {
+    const PACKED_SIZE: Option<usize> = None;
+
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        buf.append(self)
+    }
+
+    fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        write_packed_right_padded(buf, self)
+    }
+}
+
+/// `Cow` byte slices are decoded into an owned vector, `decode_borrowed` borrows them from the
+/// input instead.
+impl<'a, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, { ALIGN }, { SOL_MODE }>
+    for Cow<'a, [u8]>
+{
+    const HEADER_SIZE: usize = <Bytes as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+    const IS_DYNAMIC: bool = true;
+
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        encode_bytes::<B, ALIGN, SOL_MODE>(self, buf, offset)
+    }
+
+    fn encoded_len(&self) -> usize {
+        bytes_encoded_len::<ALIGN, SOL_MODE>(self.len())
+    }
+
+    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+        Ok(Cow::Owned(
+            read_bytes::<B, ALIGN, SOL_MODE>(buf, offset)?.to_vec(),
+        ))
+    }
+
+    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        read_bytes_header::<B, ALIGN, SOL_MODE>(buf, offset)
+    }
+}
+
+impl PackedEncoder for Cow<'_, [u8]> {
+    const PACKED_SIZE: Option<usize> = None;
+
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        self.as_ref().encode_packed(buf)
+    }
+
+    fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        self.as_ref().encode_packed_array_element(buf)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -212,7 +475,8 @@ mod tests {
 
         // For byte slice
         let bytes: &[u8] = &[1, 2, 3, 4, 5];
-        let written = write_bytes_solidity::<BigEndian, 32>(&mut buf, 0, bytes, bytes.len() as u32);
+        let written =
+            write_bytes_solidity::<BigEndian, 32>(&mut buf, 0, bytes, bytes.len() as u32).unwrap();
         assert_eq!(written, 37); // length (32) + (data + padding) (32)
         let expected = [
             0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
@@ -228,7 +492,7 @@ mod tests {
 
         let vec_u32 = [0u8, 0, 0, 10, 0, 0, 0, 20, 0, 0, 0, 30];
 
-        let written = write_bytes_solidity::<BigEndian, 32>(&mut buf, offset, &vec_u32, 3);
+        let written = write_bytes_solidity::<BigEndian, 32>(&mut buf, offset, &vec_u32, 3).unwrap();
         assert_eq!(written, 44); // length (32) + data
 
         let expected = [
@@ -299,4 +563,71 @@ mod tests {
         assert_eq!(offset, 8);
         assert_eq!(size, 5);
     }
+
+    #[test]
+    fn test_byte_containers_match_alloy_bytes() {
+        let data = b"Hello, World! This value spans more than one word.";
+        let expected = sol_data::Bytes::abi_encode(&alloy_primitives::Bytes::from_static(data));
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&Bytes::from_static(data), &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), expected);
+        let decoded = SolidityABI::<Bytes>::decode(&buf, 0).unwrap();
+        assert_eq!(&decoded[..], data);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&BytesMut::from(&data[..]), &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), expected);
+        let decoded = SolidityABI::<BytesMut>::decode(&buf, 0).unwrap();
+        assert_eq!(&decoded[..], data);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&ByteVec::from(&data[..]), &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), expected);
+        let decoded = SolidityABI::<ByteVec>::decode(&buf, 0).unwrap();
+        assert_eq!(decoded.into_inner(), data.to_vec());
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&&data[..], &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), expected);
+        assert!(SolidityABI::<&[u8]>::decode(&buf, 0).is_err());
+    }
+
+    #[test]
+    fn test_byte_vec_wasm() {
+        let original = ByteVec(vec![1, 2, 3, 4, 5]);
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        assert_eq!(hex::encode(&buf), "08000000050000000102030405000000");
+
+        let mut alloy_buf = BytesMut::new();
+        FluentABI::encode(
+            &alloy_primitives::Bytes::from(vec![1, 2, 3, 4, 5]),
+            &mut alloy_buf,
+            0,
+        )
+        .unwrap();
+        assert_eq!(buf, alloy_buf);
+
+        let decoded = FluentABI::<ByteVec>::decode(&buf, 0).unwrap();
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn test_byte_vec_is_compact() {
+        // `Vec<u8>` is `uint8[]`, while `ByteVec` is `bytes`
+        let value = (ByteVec(vec![0xAA; 3]), vec![0xAAu8; 3]);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&value, &mut buf, 0).unwrap();
+        let expected = <(sol_data::Bytes, sol_data::Array<sol_data::Uint<8>>)>::abi_encode(&(
+            alloy_primitives::Bytes::from(vec![0xAAu8; 3]),
+            vec![0xAAu8; 3],
+        ));
+        assert_eq!(buf.to_vec(), expected);
+
+        let decoded = SolidityABI::<(ByteVec, Vec<u8>)>::decode(&buf, 0).unwrap();
+        assert_eq!(decoded, value);
+    }
 }
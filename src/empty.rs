@@ -2,6 +2,7 @@ use crate::{
     alloc::string::ToString,
     encoder::{align_up, read_u32_aligned, write_u32_aligned, Encoder},
     error::{CodecError, DecodingError},
+    sink::EncodeSink,
 };
 use byteorder::ByteOrder;
 use bytes::{Buf, BytesMut};
@@ -13,23 +14,27 @@ impl<B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, false> for EmptyVec
     const HEADER_SIZE: usize = size_of::<u32>() * 3; // 12 bytes
     const IS_DYNAMIC: bool = true;
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
         let aligned_elem_size = align_up::<ALIGN>(4);
 
         // Write number of elements (0 for EmptyVec)
-        write_u32_aligned::<B, ALIGN>(buf, offset, 0);
+        write_u32_aligned::<B, ALIGN>(buf, offset, 0)?;
 
         // Write offset and length (both 0 for EmptyVec)
         write_u32_aligned::<B, ALIGN>(
             buf,
             offset + aligned_elem_size,
             (aligned_elem_size * 3) as u32,
-        );
-        write_u32_aligned::<B, ALIGN>(buf, offset + aligned_elem_size * 2, 0);
+        )?;
+        write_u32_aligned::<B, ALIGN>(buf, offset + aligned_elem_size * 2, 0)?;
 
         Ok(())
     }
 
+    fn encoded_len(&self) -> usize {
+        align_up::<ALIGN>(4) * 3
+    }
+
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
         let aligned_elem_size = align_up::<ALIGN>(4);
 
@@ -93,16 +98,20 @@ impl<B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, true> for EmptyVec
     const HEADER_SIZE: usize = 32; // Solidity uses 32 bytes for dynamic array header
     const IS_DYNAMIC: bool = true;
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
         // Write offset to data
-        write_u32_aligned::<B, ALIGN>(buf, offset, (offset + 32) as u32);
+        write_u32_aligned::<B, ALIGN>(buf, offset, (offset + 32) as u32)?;
 
         // Write length (0 for EmptyVec)
-        write_u32_aligned::<B, ALIGN>(buf, offset + 32, 0);
+        write_u32_aligned::<B, ALIGN>(buf, offset + 32, 0)?;
 
         Ok(())
     }
 
+    fn encoded_len(&self) -> usize {
+        32 + align_up::<ALIGN>(4)
+    }
+
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
         if buf.remaining() < offset + 32 {
             return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
@@ -1,13 +1,12 @@
-use crate::{alloc::string::ToString, error::CodecError};
+use crate::{
+    alloc::{borrow::Cow, string::ToString, vec, vec::Vec},
+    error::{CodecError, DecodingError},
+    sink::{EncodeSink, SubSink},
+};
 use byteorder::{ByteOrder, BE, LE};
 use bytes::{Buf, Bytes, BytesMut};
 use core::marker::PhantomData;
 
-// TODO: @d1r1 Investigate whether decoding the result into an uninitialized memory (e.g., using
-// `MaybeUninit`) would be more efficient than initializing with `Default`.
-// This could potentially reduce unnecessary memory initialization overhead in cases where
-// the default value is not required before the actual decoding takes place.
-// Consider benchmarking both approaches to measure performance differences.
 /// Trait for encoding and decoding values with specific byte order, alignment, and mode.
 ///
 /// # Type Parameters
@@ -22,12 +21,13 @@ pub trait Encoder<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>: Sized
     /// Encodes the value into the given buffer at the specified offset.
     ///
     /// # Arguments
-    /// * `buf` - The buffer to encode into.
+    /// * `buf` - The buffer to encode into, any [`EncodeSink`] like `BytesMut`, `Vec<u8>` or a
+    ///   fixed slice.
     /// * `offset` - The starting offset in the buffer for encoding.
     ///
     /// # Returns
     /// `Ok(())` if encoding was successful, or an error if encoding failed.
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError>;
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError>;
 
     /// Decodes a value from the given buffer starting at the specified offset.
     ///
@@ -49,6 +49,31 @@ pub trait Encoder<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>: Sized
     /// A tuple `(data_offset, data_length)` if successful, or an error if decoding failed.
     fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError>;
 
+    /// Encodes the values one after another, `elem_size` bytes apart starting at `offset`, the
+    /// same as encoding each of them.
+    ///
+    /// Primitive integers override it to convert the whole slice at once when their words hold
+    /// nothing but the value.
+    fn encode_slice(
+        values: &[Self],
+        buf: &mut impl EncodeSink,
+        offset: usize,
+        elem_size: usize,
+    ) -> Result<(), CodecError> {
+        encode_each::<Self, B, ALIGN, SOL_MODE>(values, buf, offset, elem_size)
+    }
+
+    /// Decodes `len` values placed `elem_size` bytes apart starting at `offset`, the counterpart
+    /// of [`Encoder::encode_slice`].
+    fn decode_slice(
+        buf: &impl Buf,
+        offset: usize,
+        len: usize,
+        elem_size: usize,
+    ) -> Result<Vec<Self>, CodecError> {
+        decode_each::<Self, B, ALIGN, SOL_MODE>(buf, offset, len, elem_size)
+    }
+
     /// Calculates the number of bytes needed to encode the value.
     ///
     /// This includes the header size and any additional space needed for alignment.
@@ -56,6 +81,168 @@ pub trait Encoder<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>: Sized
     fn size_hint(&self) -> usize {
         align_up::<ALIGN>(Self::HEADER_SIZE)
     }
+
+    /// Returns the exact number of bytes `encode` writes at offset 0 into an empty buffer: the
+    /// header and the data of all dynamic fields.
+    ///
+    /// The default implementation is exact for static types, dynamic types override it.
+    fn encoded_len(&self) -> usize {
+        align_up::<ALIGN>(Self::HEADER_SIZE)
+    }
+
+    /// Encodes the zero value of the type at the specified offset, i.e. what Solidity decodes as
+    /// the default value: zeroed static values, empty bytes, strings and arrays, and composite
+    /// values made of them. Unlike `encode` it needs no value, so `None` can carry the zero value
+    /// of any `T`.
+    ///
+    /// The default implementation is [`encode_zeroed`], composite dynamic types override it.
+    fn encode_zero(buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        encode_zeroed::<Self, B, ALIGN, SOL_MODE>(buf, offset)
+    }
+
+    /// Returns the exact number of bytes `encode_zero` writes at offset 0 into an empty buffer.
+    fn zero_encoded_len() -> usize {
+        zeroed_len::<Self, B, ALIGN, SOL_MODE>()
+    }
+}
+
+/// Zeroes the header of `T`, in Solidity mode the header of a dynamic type points to an empty
+/// tail (a zero length word). It's the default [`Encoder::encode_zero`], exact for static types,
+/// bytes, strings and arrays.
+pub fn encode_zeroed<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &mut impl EncodeSink,
+    offset: usize,
+) -> Result<(), CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    if SOL_MODE && T::IS_DYNAMIC {
+        ensure_buf_size(buf, offset + 32)?;
+        let tail_offset = buf.len();
+        write_u32_aligned::<B, ALIGN>(buf, offset, tail_offset as u32)?;
+        return buf.resize_zeroed(tail_offset + 32);
+    }
+
+    let header_size = align_up::<ALIGN>(T::HEADER_SIZE);
+    ensure_buf_size(buf, offset + header_size)?;
+    buf.fill_at(offset, header_size, 0)
+}
+
+/// The number of bytes [`encode_zeroed`] writes, the default [`Encoder::zero_encoded_len`].
+pub fn zeroed_len<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>() -> usize
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    if SOL_MODE && T::IS_DYNAMIC {
+        // The offset word, then the zero length
+        64
+    } else {
+        align_up::<ALIGN>(T::HEADER_SIZE)
+    }
+}
+
+/// Encodes the values one by one, the default [`Encoder::encode_slice`].
+pub fn encode_each<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    values: &[T],
+    buf: &mut impl EncodeSink,
+    offset: usize,
+    elem_size: usize,
+) -> Result<(), CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    for (i, value) in values.iter().enumerate() {
+        value.encode(buf, offset + elem_size * i)?;
+    }
+    Ok(())
+}
+
+/// Decodes the values one by one, the default [`Encoder::decode_slice`].
+pub fn decode_each<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &impl Buf,
+    offset: usize,
+    len: usize,
+    elem_size: usize,
+) -> Result<Vec<T>, CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    (0..len)
+        .map(|i| {
+            let elem_offset = elem_size
+                .checked_mul(i)
+                .and_then(|elem_offset| elem_offset.checked_add(offset))
+                .ok_or(CodecError::Decoding(DecodingError::Overflow))?;
+            T::decode(buf, elem_offset)
+        })
+        .collect()
+}
+
+/// Returns the number of bytes the value appends after its header when it's encoded as a field or
+/// an element of a container, zero for static types.
+pub fn encoded_tail_len<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    value: &T,
+) -> usize
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    // Dynamic values take a single offset word in Solidity mode
+    let header_size = if SOL_MODE && T::IS_DYNAMIC {
+        32
+    } else {
+        align_up::<ALIGN>(T::HEADER_SIZE)
+    };
+    value.encoded_len() - header_size
+}
+
+/// Returns the number of bytes the zero value of `T` appends after its header, the
+/// [`Encoder::encode_zero`] counterpart of [`encoded_tail_len`].
+pub fn zero_tail_len<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>() -> usize
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let header_size = if SOL_MODE && T::IS_DYNAMIC {
+        32
+    } else {
+        align_up::<ALIGN>(T::HEADER_SIZE)
+    };
+    T::zero_encoded_len() - header_size
+}
+
+/// Appends element headers `elem_size` bytes apart to the end of the buffer and encodes the
+/// elements in place, the data of dynamic elements follows the headers. Offsets inside the
+/// elements are relative to the first header, its position in the buffer is returned.
+pub(crate) fn append_elements<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &mut impl EncodeSink,
+    elements: impl ExactSizeIterator<Item = &'a T>,
+    elem_size: usize,
+) -> Result<usize, CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }> + 'a,
+{
+    let start = buf.len();
+    buf.resize_zeroed(start + elem_size * elements.len())?;
+
+    let mut elements_buf = SubSink::new(buf, start);
+    for (i, element) in elements.enumerate() {
+        element.encode(&mut elements_buf, elem_size * i)?;
+    }
+    Ok(start)
+}
+
+/// Appends the values like [`append_elements`] does, through [`Encoder::encode_slice`].
+pub(crate) fn append_slice<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &mut impl EncodeSink,
+    values: &[T],
+    elem_size: usize,
+) -> Result<usize, CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let start = buf.len();
+    buf.resize_zeroed(start + elem_size * values.len())?;
+    T::encode_slice(values, &mut SubSink::new(buf, start), 0, elem_size)?;
+    Ok(start)
 }
 
 macro_rules! define_encoder_mode {
@@ -70,24 +257,81 @@ macro_rules! define_encoder_mode {
                 <T as Encoder<$byte_order, $align, $sol_mode>>::IS_DYNAMIC
             }
 
-            pub fn encode(value: &T, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
+            pub fn encode(
+                value: &T,
+                buf: &mut impl EncodeSink,
+                offset: usize,
+            ) -> Result<(), CodecError> {
+                // Dynamic data is appended to the end, past the header at `offset`
+                buf.reserve(offset.saturating_sub(buf.len()) + value.encoded_len());
                 value.encode(buf, offset)
             }
 
+            /// Encodes a value into any `BufMut`, see
+            /// [`encode_to_buf_mut`](crate::encode_to_buf_mut).
+            pub fn encode_to_buf_mut(
+                value: &T,
+                out: &mut impl bytes::BufMut,
+            ) -> Result<(), CodecError> {
+                crate::sink::encode_to_buf_mut::<T, $byte_order, $align, $sol_mode>(value, out)
+            }
+
+            /// Decodes a value from any `Buf`, a non-contiguous one is read into a single slice
+            /// first.
             pub fn decode(buf: &impl Buf, offset: usize) -> Result<T, CodecError> {
-                T::decode(buf, offset)
+                T::decode(&&*contiguous(buf)?, offset)
+            }
+
+            /// Decodes a value from a `Buf` taken by value, that is consumed to read all of its
+            /// chunks. Use it for buffers that don't expose their chunks through `&self`, like
+            /// `VecDeque`.
+            pub fn decode_buf(mut buf: impl Buf, offset: usize) -> Result<T, CodecError> {
+                let data = buf.copy_to_bytes(buf.remaining());
+                T::decode(&data, offset)
             }
 
             pub fn partial_decode(
                 buf: &impl Buf,
                 offset: usize,
             ) -> Result<(usize, usize), CodecError> {
-                T::partial_decode(buf, offset)
+                T::partial_decode(&&*contiguous(buf)?, offset)
             }
 
             pub fn size_hint(value: &T) -> usize {
                 value.size_hint()
             }
+
+            pub fn encoded_len(value: &T) -> usize {
+                value.encoded_len()
+            }
+
+            /// Decodes the element at `index` of the `Vec<T>` encoded at `offset`, without
+            /// decoding other elements.
+            pub fn decode_element(
+                buf: &[u8],
+                offset: usize,
+                index: usize,
+            ) -> Result<T, CodecError> {
+                crate::view::decode_element::<T, $byte_order, $align, $sol_mode>(buf, offset, index)
+            }
+
+            /// Decodes a value that borrows its data from `buf`, like `&[u8]`, `&str` or a derived
+            /// struct with a lifetime.
+            pub fn decode_borrowed<'a>(buf: &'a [u8], offset: usize) -> Result<T, CodecError>
+            where
+                T: crate::borrowed::DecodeBorrowed<'a, $byte_order, $align, $sol_mode>,
+            {
+                T::decode_borrowed(buf, offset)
+            }
+
+            /// Decodes a value from a shared buffer, byte payloads like `Bytes` become refcounted
+            /// slices of `buf` instead of copies.
+            pub fn decode_shared(buf: &Bytes, offset: usize) -> Result<T, CodecError>
+            where
+                T: crate::borrowed::DecodeShared<$byte_order, $align, $sol_mode>,
+            {
+                T::decode_shared(buf, offset)
+            }
         }
     };
 }
@@ -128,21 +372,23 @@ pub fn align<B: ByteOrder, const ALIGN: usize, const SOLIDITY_COMP: bool>(src: &
 }
 
 pub fn write_u32_aligned<B: ByteOrder, const ALIGN: usize>(
-    buf: &mut BytesMut,
+    buf: &mut impl EncodeSink,
     offset: usize,
     value: u32,
-) {
+) -> Result<(), CodecError> {
     let aligned_value_size = align_up::<ALIGN>(4);
 
-    ensure_buf_size(buf, offset + aligned_value_size);
+    ensure_buf_size(buf, offset + aligned_value_size)?;
+
+    let mut bytes = [0u8; 4];
+    B::write_u32(&mut bytes, value);
 
     if is_big_endian::<B>() {
         // For big-endian, copy to the end of the aligned array
-        let start = offset + aligned_value_size - 4;
-        B::write_u32(&mut buf[start..], value);
+        buf.write_at(offset + aligned_value_size - 4, &bytes)
     } else {
         // For little-endian, copy to the start of the aligned array
-        B::write_u32(&mut buf[offset..offset + 4], value);
+        buf.write_at(offset, &bytes)
     }
 }
 
@@ -170,10 +416,11 @@ pub fn read_u32_aligned<B: ByteOrder, const ALIGN: usize>(
         ));
     }
 
+    let chunk = contiguous(buf)?;
     if is_big_endian::<B>() {
-        Ok(B::read_u32(&buf.chunk()[end_offset - 4..end_offset]))
+        Ok(B::read_u32(&chunk[end_offset - 4..end_offset]))
     } else {
-        Ok(B::read_u32(&buf.chunk()[offset..offset + 4]))
+        Ok(B::read_u32(&chunk[offset..offset + 4]))
     }
 }
 
@@ -201,11 +448,61 @@ pub fn read_u32_aligned1<B: ByteOrder, const ALIGN: usize>(
         ));
     }
 
+    let chunk = contiguous(buf)?;
     if is_big_endian::<B>() {
-        Ok(B::read_u32(&buf.chunk()[end_offset - 4..end_offset]))
+        Ok(B::read_u32(&chunk[end_offset - 4..end_offset]))
     } else {
-        Ok(B::read_u32(&buf.chunk()[offset..offset + 4]))
+        Ok(B::read_u32(&chunk[offset..offset + 4]))
+    }
+}
+
+/// Returns the remaining bytes of the buffer as a single slice. Decoders index into it, so it must
+/// hold the whole input and not only the first chunk of a non-contiguous `Buf` (e.g. a `Chain`).
+///
+/// The slice is borrowed if the buffer is contiguous (`Bytes`, `&[u8]`, ...), otherwise its chunks
+/// are copied together.
+pub fn contiguous(buf: &impl Buf) -> Result<Cow<'_, [u8]>, CodecError> {
+    let chunk = buf.chunk();
+    if chunk.len() >= buf.remaining() {
+        return Ok(Cow::Borrowed(chunk));
     }
+    gather_chunks(buf).map(Cow::Owned)
+}
+
+#[cfg(feature = "std")]
+fn gather_chunks(buf: &impl Buf) -> Result<Vec<u8>, CodecError> {
+    let mut slices = vec![std::io::IoSlice::new(&[]); 16];
+    loop {
+        let filled = buf.chunks_vectored(&mut slices);
+        let total: usize = slices[..filled].iter().map(|slice| slice.len()).sum();
+
+        if total >= buf.remaining() {
+            let mut data = Vec::with_capacity(total);
+            for slice in &slices[..filled] {
+                data.extend_from_slice(slice);
+            }
+            return Ok(data);
+        }
+
+        // The buffer doesn't expose all of its chunks, even with spare slices left
+        if filled < slices.len() {
+            return Err(non_contiguous_error());
+        }
+        slices.resize(slices.len() * 2, std::io::IoSlice::new(&[]));
+    }
+}
+
+/// Chunks past the first one can't be read through `&impl Buf` without `std`.
+#[cfg(not(feature = "std"))]
+fn gather_chunks(_buf: &impl Buf) -> Result<Vec<u8>, CodecError> {
+    Err(non_contiguous_error())
+}
+
+fn non_contiguous_error() -> CodecError {
+    CodecError::Decoding(DecodingError::InvalidData(
+        "can't read all chunks of a non-contiguous buffer, use decode_buf or copy it into `Bytes`"
+            .to_string(),
+    ))
 }
 
 /// Returns a mutable slice of the buffer at the specified offset, aligned to the specified
@@ -219,7 +516,9 @@ pub fn get_aligned_slice<B: ByteOrder, const ALIGN: usize>(
     let word_size = align_up::<ALIGN>(ALIGN.max(value_size));
 
     // Ensure the buffer is large enough
-    ensure_buf_size(buf, aligned_offset + word_size);
+    if buf.len() < aligned_offset + word_size {
+        buf.resize(aligned_offset + word_size, 0);
+    }
 
     let write_offset = if is_big_endian::<B>() {
         // For big-endian, return slice at the end of the aligned space
@@ -232,6 +531,25 @@ pub fn get_aligned_slice<B: ByteOrder, const ALIGN: usize>(
     &mut buf[write_offset..write_offset + value_size]
 }
 
+/// Writes the value into the aligned word at the specified offset, at the same place
+/// [`get_aligned_slice`] returns, and sets the rest of the word to `fill`.
+pub fn write_aligned_word<B: ByteOrder, const ALIGN: usize>(
+    buf: &mut impl EncodeSink,
+    offset: usize,
+    value: &[u8],
+    fill: u8,
+) -> Result<(), CodecError> {
+    let word_start = align_up::<ALIGN>(offset);
+    let word_end = word_start + align_up::<ALIGN>(ALIGN.max(value.len()));
+
+    ensure_buf_size(buf, word_end)?;
+
+    let (start, end) = get_aligned_indices::<B, ALIGN>(offset, value.len());
+    buf.fill_at(word_start, start - word_start, fill)?;
+    buf.write_at(start, value)?;
+    buf.fill_at(end, word_end - end, fill)
+}
+
 pub fn get_aligned_indices<B: ByteOrder, const ALIGN: usize>(
     offset: usize,
     value_size: usize,
@@ -250,11 +568,34 @@ pub fn get_aligned_indices<B: ByteOrder, const ALIGN: usize>(
     (write_offset, write_offset + value_size)
 }
 
+/// Checks that all bytes of the word outside of `start..end` are equal to `fill`.
+/// Used by strict decoders to reject values with dirty padding.
+pub fn check_word_padding(
+    word: &[u8],
+    start: usize,
+    end: usize,
+    fill: u8,
+) -> Result<(), CodecError> {
+    let is_valid = word[..start].iter().all(|byte| *byte == fill)
+        && word[end..].iter().all(|byte| *byte == fill);
+
+    if !is_valid {
+        return Err(CodecError::Decoding(
+            crate::error::DecodingError::InvalidData(
+                "word padding doesn't match the value".to_string(),
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
 /// Ensure the buffer is large enough to hold the data
-pub fn ensure_buf_size(buf: &mut BytesMut, required_size: usize) {
+pub fn ensure_buf_size(buf: &mut impl EncodeSink, required_size: usize) -> Result<(), CodecError> {
     if buf.len() < required_size {
-        buf.resize(required_size, 0);
+        buf.resize_zeroed(required_size)?;
     }
+    Ok(())
 }
 
 pub fn is_dynamic<
@@ -265,3 +606,342 @@ pub fn is_dynamic<
 >() -> bool {
     T::IS_DYNAMIC
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::String;
+    use alloy_primitives::{Address, Log, B256, U256};
+    use bytes::buf::Chain;
+    use hashbrown::HashMap;
+
+    /// The encoded value split into two chunks at every possible position.
+    fn chains(encoded: &[u8]) -> impl Iterator<Item = Chain<&[u8], &[u8]>> {
+        (0..=encoded.len()).map(move |at| {
+            let (first, second) = encoded.split_at(at);
+            first.chain(second)
+        })
+    }
+
+    #[test]
+    fn test_decode_chained_buf_sol() {
+        type Value = (u64, String, Vec<U256>, Address);
+        let original: Value = (
+            42,
+            "split across chunks".into(),
+            vec![U256::from(1), U256::MAX],
+            Address::repeat_byte(0x07),
+        );
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+
+        for chain in chains(&buf) {
+            assert_eq!(SolidityABI::<Value>::decode(&chain, 0).unwrap(), original);
+            assert_eq!(
+                <Value as Encoder<BE, 32, true>>::decode(&chain, 0).unwrap(),
+                original
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_chained_buf_wasm() {
+        let strings: Vec<String> = vec!["a".into(), "".into(), "longer string".into()];
+        let map: HashMap<u32, String> = (0..5u32).map(|i| (i, "x".repeat(i as usize))).collect();
+
+        let mut strings_buf = BytesMut::new();
+        FluentABI::encode(&strings, &mut strings_buf, 0).unwrap();
+        let mut map_buf = BytesMut::new();
+        FluentABI::encode(&map, &mut map_buf, 0).unwrap();
+
+        for chain in chains(&strings_buf) {
+            assert_eq!(
+                FluentABI::<Vec<String>>::decode(&chain, 0).unwrap(),
+                strings
+            );
+            assert_eq!(
+                <Vec<String> as Encoder<LE, 4, false>>::decode(&chain, 0).unwrap(),
+                strings
+            );
+            assert_eq!(
+                FluentABI::<Vec<String>>::partial_decode(&chain, 0).unwrap(),
+                FluentABI::<Vec<String>>::partial_decode(&&strings_buf[..], 0).unwrap()
+            );
+        }
+        for chain in chains(&map_buf) {
+            assert_eq!(
+                FluentABI::<HashMap<u32, String>>::decode(&chain, 0).unwrap(),
+                map
+            );
+        }
+    }
+
+    /// Checks that `encoded_len` is the length of the encoded value and that encoding allocates
+    /// exactly once.
+    macro_rules! assert_encoded_len {
+        ($mode:ident: $($value:expr),+ $(,)?) => {$(
+            let value = $value;
+            let mut buf = BytesMut::new();
+            $mode::encode(&value, &mut buf, 0).unwrap();
+            assert_eq!($mode::encoded_len(&value), buf.len(), "{:?}", value);
+            // Small allocations are rounded up to 8 bytes
+            assert_eq!(buf.capacity(), buf.len().max(8), "{:?}", value);
+        )+};
+    }
+
+    fn log() -> Log {
+        Log::new(
+            Address::repeat_byte(0x33),
+            vec![B256::repeat_byte(0x44); 2],
+            alloy_primitives::Bytes::from(vec![5u8; 40]),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_encoded_len_sol() {
+        use crate::{ByteOrdered, Fixed128x18, SolidityMap};
+        use alloc::collections::{BTreeMap, BTreeSet};
+        use alloy_primitives::{Bytes as AlloyBytes, FixedBytes, I256};
+
+        assert_encoded_len!(SolidityABI:
+            true,
+            0xABu8,
+            -5i64,
+            U256::MAX,
+            I256::MINUS_ONE,
+            Address::repeat_byte(0x11),
+            FixedBytes::<4>::repeat_byte(0x22),
+            Fixed128x18::try_from("-1.5").unwrap(),
+            String::new(),
+            "x".repeat(33),
+            AlloyBytes::from(vec![1u8; 40]),
+            Bytes::from_static(b"bytes"),
+            Vec::<u32>::new(),
+            vec![1u32, 2, 3],
+            vec!["a".to_string(), "b".repeat(64)],
+            vec![vec![1u8], vec![], vec![2, 3]],
+            [1u64, 2],
+            ["a".to_string(), "b".repeat(40)],
+            Some(7u32),
+            None::<u32>,
+            Some("option".to_string()),
+            None::<String>,
+            Ok::<String, u32>("ok".repeat(20)),
+            Err::<String, u32>(1),
+            (1u32, "tuple".to_string(), vec![U256::from(1)], Address::ZERO),
+            ("single".to_string(),),
+            (1u8, 2u16),
+            (0..5u32).map(|i| (i, "x".repeat(i as usize))).collect::<HashMap<_, _>>(),
+            (0..5u32).collect::<BTreeSet<_>>(),
+            ByteOrdered((0..3u32).map(|i| (i, vec![i; 2])).collect::<HashMap<_, _>>()),
+            SolidityMap((0..3u32).map(|i| (i, "v".repeat(i as usize))).collect::<BTreeMap<_, _>>()),
+            crate::empty::EmptyVec,
+            log(),
+            Some(log()),
+            None::<Log>,
+        );
+    }
+
+    #[test]
+    fn test_encoded_len_wasm() {
+        use crate::{ByteOrdered, SolidityMap};
+        use alloc::collections::{BTreeMap, BTreeSet};
+        use alloy_primitives::{Bytes as AlloyBytes, FixedBytes, I256};
+
+        assert_encoded_len!(FluentABI:
+            true,
+            0xABu8,
+            -5i64,
+            U256::MAX,
+            I256::MINUS_ONE,
+            Address::repeat_byte(0x11),
+            FixedBytes::<4>::repeat_byte(0x22),
+            String::new(),
+            "x".repeat(33),
+            AlloyBytes::from(vec![1u8; 5]),
+            Bytes::from_static(b"bytes"),
+            Vec::<u32>::new(),
+            vec![1u8, 2, 3],
+            vec!["a".to_string(), "bcd".to_string()],
+            vec![vec![1u16], vec![], vec![2, 3]],
+            [1u64, 2],
+            ["a".to_string(), "b".repeat(7)],
+            Some(7u32),
+            None::<u32>,
+            Some("option".to_string()),
+            None::<String>,
+            Ok::<String, u32>("ok".to_string()),
+            Err::<String, u32>(1),
+            (1u32, "tuple".to_string(), vec![U256::from(1)], Address::ZERO),
+            ("single".to_string(),),
+            (0..5u32).map(|i| (i, "x".repeat(i as usize))).collect::<HashMap<_, _>>(),
+            (0..5u32).collect::<BTreeSet<_>>(),
+            ByteOrdered((0..3u32).map(|i| (i, vec![i; 2])).collect::<HashMap<_, _>>()),
+            SolidityMap((0..3u32).map(|i| (i, "v".repeat(i as usize))).collect::<BTreeMap<_, _>>()),
+            crate::empty::EmptyVec,
+            log(),
+            Some(log()),
+            None::<Log>,
+        );
+    }
+
+    /// Checks `encoded_len` of a dynamic type, whose `encoded_len` must be overridden.
+    macro_rules! assert_dynamic_encoded_len {
+        ($mode:ident: $($value:expr),+ $(,)?) => {$(
+            let value = $value;
+            assert!(is_dynamic_value::<_, $mode<_>>(&value), "{:?}", value);
+            assert_encoded_len!($mode: value);
+        )+};
+    }
+
+    fn is_dynamic_value<T, M: DynamicCheck<T>>(_value: &T) -> bool {
+        M::is_dynamic()
+    }
+
+    trait DynamicCheck<T> {
+        fn is_dynamic() -> bool;
+    }
+
+    impl<T: Encoder<BE, 32, true>> DynamicCheck<T> for SolidityABI<T> {
+        fn is_dynamic() -> bool {
+            SolidityABI::<T>::is_dynamic()
+        }
+    }
+
+    impl<T: Encoder<LE, 4, false>> DynamicCheck<T> for FluentABI<T> {
+        fn is_dynamic() -> bool {
+            FluentABI::<T>::is_dynamic()
+        }
+    }
+
+    /// Every dynamic `Encoder` impl of the crate has to be listed here, the default
+    /// `encoded_len` only counts the header.
+    macro_rules! dynamic_values {
+        ($mode:ident) => {{
+            use crate::{ByteOrdered, ByteVec, SolidityMap};
+            use alloc::{
+                borrow::Cow,
+                collections::{BTreeMap, BTreeSet},
+            };
+            use hashbrown::HashSet;
+
+            assert_dynamic_encoded_len!($mode:
+                Bytes::from_static(b"bytes"),
+                BytesMut::from(&b"bytes mut"[..]),
+                ByteVec(vec![1u8; 33]),
+                &b"slice"[..],
+                Cow::<[u8]>::Borrowed(b"cow"),
+                alloy_primitives::Bytes::from(vec![2u8; 40]),
+                "string".repeat(7),
+                "str",
+                Cow::<str>::Borrowed("cow str"),
+                vec![vec![1u32], vec![]],
+                crate::empty::EmptyVec,
+                (0..3u32).map(|i| (i, "v".repeat(i as usize))).collect::<HashMap<_, _>>(),
+                (0..3u32).collect::<HashSet<_>>(),
+                (0..3u32).map(|i| (i, vec![i])).collect::<BTreeMap<_, _>>(),
+                ["a".to_string(), "b".to_string()].into_iter().collect::<BTreeSet<_>>(),
+                ByteOrdered((0..3i32).map(|i| (-i, "v".to_string())).collect::<HashMap<_, _>>()),
+                ByteOrdered((0..3i32).map(|i| -i).collect::<HashSet<_>>()),
+                SolidityMap((0..3u32).map(|i| (i, vec![i; 2])).collect::<BTreeMap<_, _>>()),
+                log(),
+                Some("option".to_string()),
+                Ok::<Vec<u8>, u32>(vec![1, 2]),
+                ["a".to_string(), "bc".to_string()],
+                (vec![1u64],),
+                (1u32, "tuple".to_string()),
+            );
+
+            #[cfg(feature = "std")]
+            {
+                use std::collections::{HashMap as StdHashMap, HashSet as StdHashSet};
+
+                assert_dynamic_encoded_len!($mode:
+                    (0..3u32).map(|i| (i, "v".to_string())).collect::<StdHashMap<_, _>>(),
+                    (0..3u32).collect::<StdHashSet<_>>(),
+                    ByteOrdered((0..3u32).map(|i| (i, i)).collect::<StdHashMap<_, _>>()),
+                    ByteOrdered((0..3u32).collect::<StdHashSet<_>>()),
+                );
+            }
+
+            #[cfg(feature = "indexmap")]
+            {
+                use indexmap::{IndexMap, IndexSet};
+
+                assert_dynamic_encoded_len!($mode:
+                    [(2u32, "b".to_string()), (1, "a".to_string())]
+                        .into_iter()
+                        .collect::<IndexMap<_, _>>(),
+                    [2u32, 1].into_iter().collect::<IndexSet<_>>(),
+                );
+            }
+        }};
+    }
+
+    #[test]
+    fn test_encoded_len_every_dynamic_type() {
+        dynamic_values!(SolidityABI);
+        dynamic_values!(FluentABI);
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_encoded_len_derived() {
+        use crate::Codec;
+
+        #[derive(Codec, Clone, Debug, Default, PartialEq)]
+        struct Static {
+            a: u32,
+            b: Address,
+        }
+
+        #[derive(Codec, Clone, Debug, Default, PartialEq)]
+        struct Dynamic {
+            id: u64,
+            name: String,
+            values: Vec<U256>,
+        }
+
+        let dynamic = Dynamic {
+            id: 1,
+            name: "dynamic".repeat(5),
+            values: vec![U256::from(1), U256::from(2)],
+        };
+        let fixed = Static {
+            a: 1,
+            b: Address::repeat_byte(1),
+        };
+        assert_encoded_len!(SolidityABI: Dynamic::default(), dynamic.clone(), fixed.clone());
+        assert_encoded_len!(FluentABI: Dynamic::default(), dynamic, fixed);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_decode_ring_buffer() {
+        use std::collections::VecDeque;
+
+        let original = vec![1u32, 2, 3, 4, 5, 6, 7, 8];
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+
+        // Wrap the data around the end of the ring buffer, so it's stored in two slices
+        let mut deque = VecDeque::with_capacity(buf.len());
+        deque.extend(core::iter::repeat(0u8).take(buf.len() / 2));
+        deque.extend(buf.iter().take(buf.len() / 2));
+        deque.drain(..buf.len() / 2);
+        deque.extend(buf.iter().skip(buf.len() / 2));
+        assert!(deque.as_slices().0.len() < buf.len());
+
+        // `VecDeque` exposes only its first slice through `&self`
+        assert!(matches!(
+            SolidityABI::<Vec<u32>>::decode(&deque, 0),
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+        assert_eq!(
+            SolidityABI::<Vec<u32>>::decode_buf(deque, 0).unwrap(),
+            original
+        );
+    }
+}
@@ -1,12 +1,37 @@
 use crate::{
-    alloc::string::ToString,
-    bytes::{read_bytes, read_bytes_header, write_bytes},
-    encoder::{align_up, get_aligned_slice, is_big_endian, write_u32_aligned, Encoder},
-    error::{CodecError, DecodingError},
+    alloc::{format, string::ToString, vec::Vec},
+    bytes::{bytes_encoded_len, read_bytes, read_bytes_header, write_bytes},
+    encoder::{
+        align_up,
+        check_word_padding,
+        contiguous,
+        ensure_buf_size,
+        is_big_endian,
+        write_aligned_word,
+        write_u32_aligned,
+        Encoder,
+    },
+    error::{CodecError, DecodingError, EncodingError},
+    packed::{write_packed_right_padded, write_packed_word, PackedEncoder, PACKED_WORD_SIZE},
+    sink::EncodeSink,
+};
+use alloy_primitives::{
+    Address,
+    Bloom,
+    Bytes,
+    FixedBytes,
+    Function,
+    Log,
+    Parity,
+    Signature,
+    Signed,
+    TxKind,
+    Uint,
+    B256,
+    U256,
 };
-use alloy_primitives::{Address, Bytes, FixedBytes, Uint};
 use byteorder::ByteOrder;
-use bytes::{Buf, BytesMut};
+use bytes::Buf;
 
 impl<B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, true> for Bytes {
     const HEADER_SIZE: usize = 32;
@@ -15,29 +40,33 @@ impl<B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, true> for Bytes {
     /// Encode the bytes into the buffer for Solidity mode.
     /// First, we encode the header and write it to the given offset.
     /// After that, we encode the actual data and write it to the end of the buffer.
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
         let aligned_header_size = align_up::<32>(<Self as Encoder<B, ALIGN, true>>::HEADER_SIZE);
 
         // Ensure the buffer has enough space for the offset + header size
         if buf.len() < offset + aligned_header_size {
-            buf.resize(offset + aligned_header_size, 0);
+            buf.resize_zeroed(offset + aligned_header_size)?;
         }
 
         // Write the offset of the data (current length of the buffer)
-        write_u32_aligned::<B, ALIGN>(buf, offset, buf.len() as u32);
+        write_u32_aligned::<B, ALIGN>(buf, offset, buf.len() as u32)?;
 
         // Write the actual data to the buffer at the current length
-        let _ = write_bytes::<B, ALIGN, true>(buf, buf.len(), self, self.len() as u32);
+        write_bytes::<B, ALIGN, true>(buf, buf.len(), self, self.len() as u32)?;
 
         // Add padding if necessary to ensure the buffer remains aligned
         if buf.len() % ALIGN != 0 {
             let padding = ALIGN - (buf.len() % ALIGN);
-            buf.resize(buf.len() + padding, 0);
+            buf.resize_zeroed(buf.len() + padding)?;
         }
 
         Ok(())
     }
 
+    fn encoded_len(&self) -> usize {
+        bytes_encoded_len::<ALIGN, true>(self.len())
+    }
+
     /// Decode the bytes from the buffer for Solidity mode.
     /// Reads the header to get the data offset and size, then reads the actual data.
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
@@ -58,25 +87,29 @@ impl<B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, false> for Bytes {
     /// Encode the bytes into the buffer.
     /// First, we encode the header and write it to the given offset.
     /// After that, we encode the actual data and write it to the end of the buffer.
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
         let aligned_el_size = align_up::<ALIGN>(4);
 
         // Ensure the buffer has enough space for the offset and header size
         if buf.len() < offset + aligned_el_size {
-            buf.resize(offset + aligned_el_size, 0);
+            buf.resize_zeroed(offset + aligned_el_size)?;
         }
 
-        let _ = write_bytes::<B, ALIGN, false>(buf, offset, self, self.len() as u32);
+        write_bytes::<B, ALIGN, false>(buf, offset, self, self.len() as u32)?;
 
         // Add padding if necessary to ensure the buffer remains aligned
         if buf.len() % ALIGN != 0 {
             let padding = ALIGN - (buf.len() % ALIGN);
-            buf.resize(buf.len() + padding, 0);
+            buf.resize_zeroed(buf.len() + padding)?;
         }
 
         Ok(())
     }
 
+    fn encoded_len(&self) -> usize {
+        bytes_encoded_len::<ALIGN, false>(self.len())
+    }
+
     /// Decode the bytes from the buffer.
     /// Reads the header to get the data offset and size, then read the actual data.
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
@@ -89,6 +122,18 @@ impl<B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, false> for Bytes {
         read_bytes_header::<B, ALIGN, false>(buf, offset)
     }
 }
+impl PackedEncoder for Bytes {
+    const PACKED_SIZE: Option<usize> = None;
+
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        buf.append(self)
+    }
+
+    fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        write_packed_right_padded(buf, self)
+    }
+}
+
 impl<const N: usize, B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, false>
     for FixedBytes<N>
 {
@@ -97,10 +142,8 @@ impl<const N: usize, B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, fal
 
     /// Encode the fixed bytes into the buffer.
     /// Writes the fixed bytes directly to the buffer at the given offset.
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
-        let slice = get_aligned_slice::<B, ALIGN>(buf, offset, N);
-        slice.copy_from_slice(self.as_ref());
-        Ok(())
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        write_aligned_word::<B, ALIGN>(buf, offset, self.as_ref(), 0)
     }
 
     /// Decode the fixed bytes from the buffer.
@@ -113,8 +156,9 @@ impl<const N: usize, B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, fal
                 msg: "Buffer too small to decode FixedBytes".to_string(),
             }));
         }
-        let data = buf.chunk()[offset..offset + N].to_vec();
-        Ok(FixedBytes::from_slice(&data))
+        Ok(FixedBytes::from_slice(
+            &contiguous(buf)?[offset..offset + N],
+        ))
     }
 
     /// Partially decode the fixed bytes from the buffer.
@@ -125,42 +169,60 @@ impl<const N: usize, B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, fal
     }
 }
 
+/// In Solidity mode `bytesN` is right-padded to 32 bytes. Values longer than a word (e.g. `Bloom`)
+/// are laid out like `bytes32[]` elements: right-padded to a multiple of 32 bytes.
 impl<const N: usize, B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, true>
     for FixedBytes<N>
 {
-    const HEADER_SIZE: usize = 32; // Always 32 bytes for Solidity ABI
+    const HEADER_SIZE: usize = if N <= 32 { 32 } else { align_up::<32>(N) };
     const IS_DYNAMIC: bool = false;
 
     /// Encode the fixed bytes into the buffer for Solidity mode.
     /// Writes the fixed bytes directly to the buffer at the given offset, zero-padding to 32 bytes.
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
-        let slice = get_aligned_slice::<B, 32>(buf, offset, 32);
-        slice[..N].copy_from_slice(self.as_ref());
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        let header_size = <Self as Encoder<B, ALIGN, true>>::HEADER_SIZE;
+        let word_start = align_up::<32>(offset);
+        ensure_buf_size(buf, word_start + header_size)?;
+        buf.write_at(word_start, self.as_ref())?;
         // Zero-pad the rest
-        slice[N..].fill(0);
-        Ok(())
+        buf.fill_at(word_start + N, header_size - N, 0)
     }
 
     /// Decode the fixed bytes from the buffer for Solidity mode.
     /// Reads the fixed bytes directly from the buffer at the given offset, assuming 32-byte
-    /// alignment.
+    /// alignment. The padding must be zeroed.
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+        let header_size = <Self as Encoder<B, ALIGN, true>>::HEADER_SIZE;
         let offset = align_up::<32>(offset); // Always 32-byte aligned for Solidity
-        if buf.remaining() < offset + 32 {
+        if buf.remaining() < offset + header_size {
             return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
-                expected: offset + 32,
+                expected: offset + header_size,
                 found: buf.remaining(),
                 msg: "Buffer too small to decode FixedBytes".to_string(),
             }));
         }
-        let data = buf.chunk()[offset..offset + N].to_vec();
-        Ok(FixedBytes::from_slice(&data))
+        let chunk = &contiguous(buf)?[offset..offset + header_size];
+        check_word_padding(chunk, 0, N, 0x00)?;
+        Ok(FixedBytes::from_slice(&chunk[..N]))
     }
 
     /// Partially decode the fixed bytes from the buffer for Solidity mode.
     /// Returns the data offset and size without reading the actual data.
     fn partial_decode(_buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
-        Ok((offset, 32))
+        Ok((offset, <Self as Encoder<B, ALIGN, true>>::HEADER_SIZE))
+    }
+}
+
+impl<const N: usize> PackedEncoder for FixedBytes<N> {
+    const PACKED_SIZE: Option<usize> = Some(N);
+    const PACKED_ELEMENT_SIZE: Option<usize> = Some(N.next_multiple_of(PACKED_WORD_SIZE));
+
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        buf.append(self.as_ref())
+    }
+
+    fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        write_packed_right_padded(buf, self.as_ref())
     }
 }
 
@@ -172,10 +234,8 @@ macro_rules! impl_evm_fixed {
 
             /// Encode the fixed bytes into the buffer.
             /// Writes the fixed bytes directly to the buffer at the given offset.
-            fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
-                let slice = get_aligned_slice::<B, ALIGN>(buf, offset, <$type>::len_bytes());
-                slice.copy_from_slice(self.as_ref());
-                Ok(())
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                write_aligned_word::<B, ALIGN>(buf, offset, self.as_ref(), 0)
             }
 
             /// Decode the fixed bytes from the buffer.
@@ -189,8 +249,9 @@ macro_rules! impl_evm_fixed {
                         msg: "Buffer too small to decode fixed bytes".to_string(),
                     }));
                 }
-                let data = buf.chunk()[offset..offset + size].to_vec();
-                Ok(<$type>::from_slice(&data))
+                Ok(<$type>::from_slice(
+                    &contiguous(buf)?[offset..offset + size],
+                ))
             }
 
             /// Partially decode the fixed bytes from the buffer.
@@ -210,14 +271,14 @@ macro_rules! impl_evm_fixed {
             /// Encode the fixed bytes into the buffer for Solidity mode.
             /// Writes the fixed bytes directly to the buffer at the given offset, zero-padding to
             /// 32 bytes.
-            fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
-                let slice = get_aligned_slice::<B, 32>(buf, offset, 32);
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                let word_start = align_up::<32>(offset);
                 let size = <$type>::len_bytes();
+                ensure_buf_size(buf, word_start + 32)?;
                 // Zero-pad the beginning
-                slice[..32 - size].fill(0);
+                buf.fill_at(word_start, 32 - size, 0)?;
                 // Copy the address bytes to the end
-                slice[32 - size..].copy_from_slice(self.as_ref());
-                Ok(())
+                buf.write_at(word_start + 32 - size, self.as_ref())
             }
 
             /// Decode the fixed bytes from the buffer for Solidity mode.
@@ -232,8 +293,9 @@ macro_rules! impl_evm_fixed {
                         msg: "Buffer too small to decode fixed bytes".to_string(),
                     }));
                 }
-                let data = buf.chunk()[offset + 32 - size..offset + 32].to_vec();
-                Ok(<$type>::from_slice(&data))
+                Ok(<$type>::from_slice(
+                    &contiguous(buf)?[offset + 32 - size..offset + 32],
+                ))
             }
 
             /// Partially decode the fixed bytes from the buffer for Solidity mode.
@@ -245,21 +307,250 @@ macro_rules! impl_evm_fixed {
                 Ok((offset, 32))
             }
         }
+
+        impl PackedEncoder for $type {
+            const PACKED_SIZE: Option<usize> = Some(<$type>::len_bytes());
+            const PACKED_ELEMENT_SIZE: Option<usize> = Some(PACKED_WORD_SIZE);
+
+            fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+                buf.append(self.as_ref())
+            }
+
+            fn encode_packed_array_element(
+                &self,
+                buf: &mut impl EncodeSink,
+            ) -> Result<(), CodecError> {
+                write_packed_word(buf, self.as_ref(), 0x00)
+            }
+        }
     };
 }
 
 impl_evm_fixed!(Address);
 
+/// Implements encoding for references to fixed bytes, they can't be decoded by [`Encoder::decode`]
+/// since the input isn't borrowed, use `decode_borrowed` instead.
+macro_rules! impl_evm_fixed_ref {
+    ($type:ty $(, const $n:ident)?) => {
+        impl<'a, $(const $n: usize,)? B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+            Encoder<B, { ALIGN }, { SOL_MODE }> for &'a $type
+        where
+            $type: Encoder<B, { ALIGN }, { SOL_MODE }>,
+        {
+            const HEADER_SIZE: usize = <$type as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+            const IS_DYNAMIC: bool = false;
+
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                <$type as Encoder<B, ALIGN, SOL_MODE>>::encode(*self, buf, offset)
+            }
+
+            fn decode(_buf: &impl Buf, _offset: usize) -> Result<Self, CodecError> {
+                Err(CodecError::Decoding(DecodingError::InvalidData(
+                    "can't decode into a borrowed fixed bytes reference, use decode_borrowed \
+                     instead"
+                        .to_string(),
+                )))
+            }
+
+            fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+                <$type as Encoder<B, ALIGN, SOL_MODE>>::partial_decode(buf, offset)
+            }
+        }
+    };
+}
+
+impl_evm_fixed_ref!(FixedBytes<N>, const N);
+impl_evm_fixed_ref!(Address);
+
+/// Implements the codec for newtypes over `FixedBytes<N>`, they share the layout of the inner
+/// fixed bytes in every mode.
+macro_rules! impl_evm_fixed_bytes_wrapper {
+    ($type:ty, $n:expr) => {
+        impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+            Encoder<B, { ALIGN }, { SOL_MODE }> for $type
+        where
+            FixedBytes<$n>: Encoder<B, { ALIGN }, { SOL_MODE }>,
+        {
+            const HEADER_SIZE: usize = <FixedBytes<$n> as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+            const IS_DYNAMIC: bool = false;
+
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                <FixedBytes<$n> as Encoder<B, ALIGN, SOL_MODE>>::encode(&self.0, buf, offset)
+            }
+
+            fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+                <FixedBytes<$n> as Encoder<B, ALIGN, SOL_MODE>>::decode(buf, offset).map(Self::from)
+            }
+
+            fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+                <FixedBytes<$n> as Encoder<B, ALIGN, SOL_MODE>>::partial_decode(buf, offset)
+            }
+        }
+
+        impl PackedEncoder for $type {
+            const PACKED_SIZE: Option<usize> = Some($n);
+            const PACKED_ELEMENT_SIZE: Option<usize> =
+                <FixedBytes<$n> as PackedEncoder>::PACKED_ELEMENT_SIZE;
+
+            fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+                self.0.encode_packed(buf)
+            }
+
+            fn encode_packed_array_element(
+                &self,
+                buf: &mut impl EncodeSink,
+            ) -> Result<(), CodecError> {
+                self.0.encode_packed_array_element(buf)
+            }
+        }
+    };
+}
+
+// `function` is 24 bytes (address + selector), encoded exactly like `bytes24`
+impl_evm_fixed_bytes_wrapper!(Function, 24);
+impl_evm_fixed_bytes_wrapper!(Bloom, 256);
+
+/// Signature is encoded as a static `(uint256 r, uint256 s, uint64 v)` tuple, where `v` is the raw
+/// V value of the parity (0/1, 27/28 or EIP-155), so any parity round-trips.
+impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, { ALIGN }, { SOL_MODE }>
+    for Signature
+where
+    (U256, U256, u64): Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    const HEADER_SIZE: usize = <(U256, U256, u64) as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+    const IS_DYNAMIC: bool = false;
+
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        let value = (self.r(), self.s(), self.v().to_u64());
+        <(U256, U256, u64) as Encoder<B, ALIGN, SOL_MODE>>::encode(&value, buf, offset)
+    }
+
+    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+        let (r, s, v) = <(U256, U256, u64) as Encoder<B, ALIGN, SOL_MODE>>::decode(buf, offset)?;
+        let parity = Parity::try_from(v).map_err(|err| {
+            CodecError::Decoding(DecodingError::InvalidData(format!(
+                "invalid signature parity: {}",
+                err
+            )))
+        })?;
+        Ok(Signature::new(r, s, parity))
+    }
+
+    fn partial_decode(_buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        Ok((offset, <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE))
+    }
+}
+
+/// Packed signature is the common 65 bytes `r || s || v` form with `v` being 27 or 28.
+impl PackedEncoder for Signature {
+    const PACKED_SIZE: Option<usize> = Some(65);
+
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        buf.append(&self.as_bytes())
+    }
+}
+
+/// Log is encoded as a `(address, bytes32[] topics, bytes data)` tuple.
+impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, { ALIGN }, { SOL_MODE }>
+    for Log
+where
+    (Address, Vec<B256>, Bytes): Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    const HEADER_SIZE: usize =
+        <(Address, Vec<B256>, Bytes) as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+    const IS_DYNAMIC: bool = true;
+
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        <(Address, Vec<B256>, Bytes) as Encoder<B, ALIGN, SOL_MODE>>::encode(
+            &log_tuple(self),
+            buf,
+            offset,
+        )
+    }
+
+    fn encoded_len(&self) -> usize {
+        <(Address, Vec<B256>, Bytes) as Encoder<B, ALIGN, SOL_MODE>>::encoded_len(&log_tuple(self))
+    }
+
+    fn encode_zero(buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        <(Address, Vec<B256>, Bytes) as Encoder<B, ALIGN, SOL_MODE>>::encode_zero(buf, offset)
+    }
+
+    fn zero_encoded_len() -> usize {
+        <(Address, Vec<B256>, Bytes) as Encoder<B, ALIGN, SOL_MODE>>::zero_encoded_len()
+    }
+
+    /// Returns an error if the log has more than 4 topics.
+    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+        let (address, topics, data) =
+            <(Address, Vec<B256>, Bytes) as Encoder<B, ALIGN, SOL_MODE>>::decode(buf, offset)?;
+        Log::new(address, topics, data).ok_or_else(|| {
+            CodecError::Decoding(DecodingError::InvalidData(
+                "log can't have more than 4 topics".to_string(),
+            ))
+        })
+    }
+
+    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        <(Address, Vec<B256>, Bytes) as Encoder<B, ALIGN, SOL_MODE>>::partial_decode(buf, offset)
+    }
+}
+
+fn log_tuple(log: &Log) -> (Address, Vec<B256>, Bytes) {
+    (
+        log.address,
+        log.data.topics().to_vec(),
+        log.data.data.clone(),
+    )
+}
+
+/// TxKind is encoded as a `(bool is_call, address to)` tuple, `Create` has a zero address.
+impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, { ALIGN }, { SOL_MODE }>
+    for TxKind
+where
+    (bool, Address): Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    const HEADER_SIZE: usize = <(bool, Address) as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+    const IS_DYNAMIC: bool = false;
+
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        let value = match self {
+            TxKind::Create => (false, Address::ZERO),
+            TxKind::Call(address) => (true, *address),
+        };
+        <(bool, Address) as Encoder<B, ALIGN, SOL_MODE>>::encode(&value, buf, offset)
+    }
+
+    /// Returns an error for `Create` with a non-zero address.
+    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+        let (is_call, address) =
+            <(bool, Address) as Encoder<B, ALIGN, SOL_MODE>>::decode(buf, offset)?;
+        if is_call {
+            Ok(TxKind::Call(address))
+        } else if address.is_zero() {
+            Ok(TxKind::Create)
+        } else {
+            Err(CodecError::Decoding(DecodingError::InvalidData(
+                "TxKind::Create must have a zero address".to_string(),
+            )))
+        }
+    }
+
+    fn partial_decode(_buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        Ok((offset, <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE))
+    }
+}
+
 impl<const BITS: usize, const LIMBS: usize, B: ByteOrder, const ALIGN: usize>
     Encoder<B, { ALIGN }, false> for Uint<BITS, LIMBS>
 {
     const HEADER_SIZE: usize = Self::BYTES;
     const IS_DYNAMIC: bool = false;
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
         let word_size = align_up::<ALIGN>(Self::BYTES);
-
-        let slice = get_aligned_slice::<B, { ALIGN }>(buf, offset, word_size);
+        let word_start = align_up::<ALIGN>(offset);
+        ensure_buf_size(buf, word_start + word_size)?;
 
         let bytes = if is_big_endian::<B>() {
             self.to_be_bytes_vec()
@@ -267,9 +558,8 @@ impl<const BITS: usize, const LIMBS: usize, B: ByteOrder, const ALIGN: usize>
             self.to_le_bytes_vec()
         };
 
-        slice[..Self::BYTES].copy_from_slice(&bytes);
-
-        Ok(())
+        buf.write_at(word_start, &bytes)?;
+        buf.fill_at(word_start + Self::BYTES, word_size - Self::BYTES, 0)
     }
 
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
@@ -283,7 +573,7 @@ impl<const BITS: usize, const LIMBS: usize, B: ByteOrder, const ALIGN: usize>
             }));
         }
 
-        let chunk = &buf.chunk()[offset..offset + word_size];
+        let chunk = &contiguous(buf)?[offset..offset + word_size];
         let value_slice = &chunk[..Self::BYTES];
 
         let value = if is_big_endian::<B>() {
@@ -307,8 +597,9 @@ impl<const BITS: usize, const LIMBS: usize, B: ByteOrder, const ALIGN: usize>
     const HEADER_SIZE: usize = 32; // Always 32 bytes for Solidity ABI
     const IS_DYNAMIC: bool = false;
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
-        let slice = get_aligned_slice::<B, 32>(buf, offset, 32);
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        let word_start = align_up::<32>(offset);
+        ensure_buf_size(buf, word_start + 32)?;
 
         let bytes = if is_big_endian::<B>() {
             self.to_be_bytes_vec()
@@ -317,8 +608,8 @@ impl<const BITS: usize, const LIMBS: usize, B: ByteOrder, const ALIGN: usize>
         };
 
         // For Solidity ABI, right-align the data
-        slice[32 - Self::BYTES..].copy_from_slice(&bytes);
-        slice[..32 - Self::BYTES].fill(0); // Zero-pad the rest
+        buf.write_at(word_start + 32 - Self::BYTES, &bytes)?;
+        buf.fill_at(word_start, 32 - Self::BYTES, 0)?; // Zero-pad the rest
 
         Ok(())
     }
@@ -332,9 +623,12 @@ impl<const BITS: usize, const LIMBS: usize, B: ByteOrder, const ALIGN: usize>
             }));
         }
 
-        let chunk = &buf.chunk()[offset..offset + 32];
+        let chunk = &contiguous(buf)?[offset..offset + 32];
         let value_slice = &chunk[32 - Self::BYTES..];
 
+        // Solidity decoding is strict, the padding must be zeroed
+        check_word_padding(chunk, 32 - Self::BYTES, 32, 0x00)?;
+
         let value = if is_big_endian::<B>() {
             Self::from_be_slice(value_slice)
         } else {
@@ -349,6 +643,130 @@ impl<const BITS: usize, const LIMBS: usize, B: ByteOrder, const ALIGN: usize>
     }
 }
 
+/// Signed integers are stored in two's complement form and extended to the full word with their
+/// sign, exactly like Solidity's `intN`. In Solidity mode the word is always 32 bytes and decoding
+/// is strict: the padding must be a valid sign extension of the value.
+impl<
+        const BITS: usize,
+        const LIMBS: usize,
+        B: ByteOrder,
+        const ALIGN: usize,
+        const SOL_MODE: bool,
+    > Encoder<B, { ALIGN }, { SOL_MODE }> for Signed<BITS, LIMBS>
+{
+    const HEADER_SIZE: usize = if SOL_MODE { 32 } else { Self::BYTES };
+    const IS_DYNAMIC: bool = false;
+
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        let header_size = <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+        if Self::BYTES > header_size {
+            return Err(CodecError::Encoding(EncodingError::InvalidInputData(
+                "Signed wider than 256 bits can't be encoded as a Solidity word".to_string(),
+            )));
+        }
+
+        let bytes = if is_big_endian::<B>() {
+            self.into_raw().to_be_bytes_vec()
+        } else {
+            self.into_raw().to_le_bytes_vec()
+        };
+
+        // Fill the rest of the word with the sign extension
+        let fill_val = if self.is_negative() { 0xFF } else { 0x00 };
+        write_aligned_word::<B, ALIGN>(buf, offset, &bytes, fill_val)
+    }
+
+    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+        let word_size = align_up::<ALIGN>(<Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
+
+        if buf.remaining() < offset + word_size {
+            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+                expected: offset + word_size,
+                found: buf.remaining(),
+                msg: "buf too small to read Signed".to_string(),
+            }));
+        }
+
+        let word = &contiguous(buf)?[offset..offset + word_size];
+        let (start, end) = if is_big_endian::<B>() {
+            (word_size - Self::BYTES, word_size)
+        } else {
+            (0, Self::BYTES)
+        };
+
+        let raw = if is_big_endian::<B>() {
+            Uint::<BITS, LIMBS>::try_from_be_slice(&word[start..end])
+        } else {
+            Uint::<BITS, LIMBS>::try_from_le_slice(&word[start..end])
+        }
+        .ok_or_else(|| {
+            CodecError::Decoding(DecodingError::InvalidData(
+                "value doesn't fit into Signed".to_string(),
+            ))
+        })?;
+        let value = Self::from_raw(raw);
+
+        if SOL_MODE {
+            let fill_val = if value.is_negative() { 0xFF } else { 0x00 };
+            check_word_padding(word, start, end, fill_val)?;
+        }
+
+        Ok(value)
+    }
+
+    fn partial_decode(_buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        Ok((
+            offset,
+            align_up::<ALIGN>(<Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE),
+        ))
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> PackedEncoder for Signed<BITS, LIMBS> {
+    const PACKED_SIZE: Option<usize> = Some(Self::BYTES);
+    const PACKED_ELEMENT_SIZE: Option<usize> = if Self::BYTES > PACKED_WORD_SIZE {
+        None
+    } else {
+        Some(PACKED_WORD_SIZE)
+    };
+
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        buf.append(&self.into_raw().to_be_bytes_vec())
+    }
+
+    fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        if Self::BYTES > 32 {
+            return Err(CodecError::Encoding(EncodingError::InvalidInputData(
+                "Signed wider than 256 bits can't be encoded as a Solidity word".to_string(),
+            )));
+        }
+        let fill_val = if self.is_negative() { 0xFF } else { 0x00 };
+        write_packed_word(buf, &self.into_raw().to_be_bytes_vec(), fill_val)
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> PackedEncoder for Uint<BITS, LIMBS> {
+    const PACKED_SIZE: Option<usize> = Some(Self::BYTES);
+    const PACKED_ELEMENT_SIZE: Option<usize> = if Self::BYTES > PACKED_WORD_SIZE {
+        None
+    } else {
+        Some(PACKED_WORD_SIZE)
+    };
+
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        buf.append(&self.to_be_bytes_vec())
+    }
+
+    fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        if Self::BYTES > 32 {
+            return Err(CodecError::Encoding(EncodingError::InvalidInputData(
+                "Uint wider than 256 bits can't be encoded as a Solidity word".to_string(),
+            )));
+        }
+        write_packed_word(buf, &self.to_be_bytes_vec(), 0x00)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -373,7 +791,8 @@ mod tests {
         let original = Bytes::from_static(b"Hello, World");
         // Write the data to the buf
         let _result =
-            write_bytes::<BigEndian, 8, false>(&mut buf, 16, &original, original.len() as u32);
+            write_bytes::<BigEndian, 8, false>(&mut buf, 16, &original, original.len() as u32)
+                .unwrap();
 
         let expected = [
             0, 0, 0, 0, 0, 0, 0, 32, // offset of the 1st bytes
@@ -457,4 +876,241 @@ mod tests {
 
         assert_eq!(original, decoded);
     }
+
+    #[test]
+    fn test_signed_matches_solidity() {
+        use crate::encoder::{FluentABI, SolidityABI};
+        use alloy_primitives::{I160, I256};
+        use alloy_sol_types::{sol_data, SolType};
+
+        for original in [
+            I256::MINUS_ONE,
+            I256::ZERO,
+            I256::MIN,
+            I256::try_from(42).unwrap(),
+        ] {
+            let mut buf = BytesMut::new();
+            SolidityABI::encode(&original, &mut buf, 0).unwrap();
+            assert_eq!(buf.to_vec(), sol_data::Int::<256>::abi_encode(&original));
+            assert_eq!(SolidityABI::<I256>::decode(&buf, 0).unwrap(), original);
+        }
+
+        let original = I160::try_from(-3).unwrap();
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        assert_eq!(hex::encode(&buf), format!("{}fd", "ff".repeat(31)));
+        assert_eq!(SolidityABI::<I160>::decode(&buf, 0).unwrap(), original);
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        assert_eq!(hex::encode(&buf), format!("fd{}", "ff".repeat(19)));
+        assert_eq!(FluentABI::<I160>::decode(&buf, 0).unwrap(), original);
+    }
+
+    #[test]
+    fn test_evm_ints_strict_decoding() {
+        use crate::encoder::SolidityABI;
+        use alloy_primitives::{I160, U160};
+
+        // A non-negative int160 must be padded with zeroes
+        let mut word = [0u8; 32];
+        word[0] = 0xFF;
+        word[31] = 0x01;
+        assert!(SolidityABI::<I160>::decode(&&word[..], 0).is_err());
+        assert!(SolidityABI::<U160>::decode(&&word[..], 0).is_err());
+
+        word[0] = 0x00;
+        assert_eq!(
+            SolidityABI::<U160>::decode(&&word[..], 0).unwrap(),
+            U160::from(1)
+        );
+    }
+
+    #[test]
+    fn test_function_and_b256_match_solidity() {
+        use crate::encoder::SolidityABI;
+        use alloy_sol_types::{sol_data, SolType};
+
+        let function =
+            Function::from_address_and_selector(Address::repeat_byte(0xAA), [1, 2, 3, 4]);
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&function, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), sol_data::Function::abi_encode(&function));
+        assert_eq!(SolidityABI::<Function>::decode(&buf, 0).unwrap(), function);
+
+        let hash = B256::repeat_byte(0x42);
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&hash, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), sol_data::FixedBytes::<32>::abi_encode(&hash));
+        assert_eq!(SolidityABI::<B256>::decode(&buf, 0).unwrap(), hash);
+    }
+
+    #[test]
+    fn test_bloom_encode_decode() {
+        use crate::encoder::{FluentABI, SolidityABI};
+
+        let mut bloom = Bloom::default();
+        bloom.accrue_raw_log(Address::repeat_byte(0x11), &[B256::repeat_byte(0x22)]);
+
+        // Solidity layout is the same as `bytes32[8]`
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&bloom, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), bloom.as_slice());
+        assert_eq!(SolidityABI::<Bloom>::decode(&buf, 0).unwrap(), bloom);
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&(bloom, 7u32), &mut buf, 0).unwrap();
+        assert_eq!(buf.len(), 256 + 4);
+        assert_eq!(
+            FluentABI::<(Bloom, u32)>::decode(&buf, 0).unwrap(),
+            (bloom, 7u32)
+        );
+    }
+
+    #[test]
+    fn test_signature_encode_decode() {
+        use crate::encoder::{FluentABI, SolidityABI};
+        use alloy_sol_types::SolValue;
+
+        let r = U256::from(0x1234);
+        let s = U256::from(0x5678);
+
+        for parity in [
+            Parity::Parity(true),
+            Parity::NonEip155(false),
+            Parity::Eip155(37),
+        ] {
+            let signature = Signature::new(r, s, parity);
+
+            let mut buf = BytesMut::new();
+            SolidityABI::encode(&signature, &mut buf, 0).unwrap();
+            assert_eq!(buf.to_vec(), (r, s, parity.to_u64()).abi_encode());
+            assert_eq!(
+                SolidityABI::<Signature>::decode(&buf, 0).unwrap(),
+                signature
+            );
+
+            let mut buf = BytesMut::new();
+            FluentABI::encode(&signature, &mut buf, 0).unwrap();
+            assert_eq!(FluentABI::<Signature>::decode(&buf, 0).unwrap(), signature);
+        }
+
+        // v = 5 is not a valid parity
+        let buf = (r, s, 5u64).abi_encode();
+        assert!(matches!(
+            SolidityABI::<Signature>::decode(&&buf[..], 0),
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+    }
+
+    #[test]
+    fn test_log_encode_decode() {
+        use crate::encoder::{FluentABI, SolidityABI};
+        use alloy_sol_types::SolValue;
+
+        let log = Log::new(
+            Address::repeat_byte(0x01),
+            vec![B256::repeat_byte(0x02), B256::repeat_byte(0x03)],
+            Bytes::from_static(b"log data"),
+        )
+        .unwrap();
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&log, &mut buf, 0).unwrap();
+        let expected = (
+            log.address,
+            log.data.topics().to_vec(),
+            log.data.data.clone(),
+        )
+            .abi_encode();
+        assert_eq!(buf.to_vec(), expected);
+        assert_eq!(SolidityABI::<Log>::decode(&buf, 0).unwrap(), log);
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&log, &mut buf, 0).unwrap();
+        assert_eq!(FluentABI::<Log>::decode(&buf, 0).unwrap(), log);
+
+        // More than 4 topics is not a valid log
+        let mut buf = BytesMut::new();
+        let value = (Address::ZERO, vec![B256::ZERO; 5], Bytes::new());
+        SolidityABI::encode(&value, &mut buf, 0).unwrap();
+        assert!(SolidityABI::<Log>::decode(&buf, 0).is_err());
+    }
+
+    #[test]
+    fn test_log_zero_value_sol() {
+        use crate::encoder::SolidityABI;
+        use alloy_sol_types::{sol_data, SolType};
+
+        // `None` carries the zero value `(address(0), bytes32[](), "")`
+        type SolOption = (
+            sol_data::Bool,
+            (
+                sol_data::Address,
+                sol_data::Array<sol_data::FixedBytes<32>>,
+                sol_data::Bytes,
+            ),
+        );
+        let zero = (false, (Address::ZERO, Vec::<B256>::new(), Bytes::new()));
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&None::<Log>, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), SolOption::abi_encode(&zero));
+        assert!(SolOption::abi_decode(&buf, true).is_ok());
+        assert_eq!(SolidityABI::encoded_len(&None::<Log>), buf.len());
+        assert_eq!(SolidityABI::<Option<Log>>::decode(&buf, 0).unwrap(), None);
+    }
+
+    #[test]
+    fn test_tx_kind_encode_decode() {
+        use crate::encoder::{FluentABI, SolidityABI};
+        use alloy_primitives::TxKind;
+        use alloy_sol_types::SolValue;
+
+        let call = TxKind::Call(Address::repeat_byte(0xCC));
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&call, &mut buf, 0).unwrap();
+        assert_eq!(
+            buf.to_vec(),
+            (true, Address::repeat_byte(0xCC)).abi_encode()
+        );
+        assert_eq!(SolidityABI::<TxKind>::decode(&buf, 0).unwrap(), call);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&TxKind::Create, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), (false, Address::ZERO).abi_encode());
+        assert_eq!(
+            SolidityABI::<TxKind>::decode(&buf, 0).unwrap(),
+            TxKind::Create
+        );
+
+        for kind in [call, TxKind::Create] {
+            let mut buf = BytesMut::new();
+            FluentABI::encode(&kind, &mut buf, 0).unwrap();
+            assert_eq!(FluentABI::<TxKind>::decode(&buf, 0).unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn test_tx_kind_create_with_address() {
+        use crate::encoder::{FluentABI, SolidityABI};
+        use alloy_primitives::TxKind;
+
+        let value = (false, Address::repeat_byte(0xCC));
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&value, &mut buf, 0).unwrap();
+        assert!(matches!(
+            SolidityABI::<TxKind>::decode(&buf, 0),
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&value, &mut buf, 0).unwrap();
+        assert!(matches!(
+            FluentABI::<TxKind>::decode(&buf, 0),
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+    }
 }
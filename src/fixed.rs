@@ -0,0 +1,549 @@
+use crate::{
+    alloc::{
+        format,
+        string::{String, ToString},
+    },
+    encoder::Encoder,
+    error::{CodecError, DecodingError},
+    packed::{PackedEncoder, PACKED_WORD_SIZE},
+    sink::EncodeSink,
+};
+use alloy_primitives::{Sign, Signed, Uint, I256, U256};
+use byteorder::ByteOrder;
+use bytes::Buf;
+use core::{fmt, str::FromStr};
+
+/// Solidity's `ufixed` is an alias for `ufixed128x18`.
+pub type UFixed128x18 = UFixed<128, 18>;
+/// Solidity's `fixed` is an alias for `fixed128x18`.
+pub type Fixed128x18 = Fixed<128, 18>;
+
+/// Returns `value * 10^exp` or `None` on overflow.
+fn checked_scale(value: U256, exp: usize) -> Option<U256> {
+    if value.is_zero() {
+        return Some(U256::ZERO);
+    }
+    U256::from(10)
+        .checked_pow(U256::from(exp))
+        .and_then(|scale| value.checked_mul(scale))
+}
+
+/// Splits the raw scaled value into its integer and fractional parts.
+fn split_scaled(value: U256, decimals: usize) -> (U256, U256) {
+    match U256::from(10).checked_pow(U256::from(decimals)) {
+        Some(scale) => (value / scale, value % scale),
+        // 10^decimals doesn't fit into 256 bits, so the integer part is always zero
+        None => (U256::ZERO, value),
+    }
+}
+
+/// Parses an unsigned decimal string like `12.345` into the value scaled by `10^decimals`.
+/// More fractional digits than `decimals` is an error, the value is never rounded.
+fn parse_scaled(value: &str, decimals: usize) -> Result<U256, CodecError> {
+    let parse_error = |msg: &str| {
+        CodecError::Decoding(DecodingError::ParseError(format!(
+            "invalid fixed-point number {:?}: {}",
+            value, msg
+        )))
+    };
+
+    let (int_part, frac_part) = match value.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, frac_part),
+        None => (value, ""),
+    };
+    if int_part.is_empty() && frac_part.is_empty() {
+        return Err(parse_error("no digits"));
+    }
+    if !int_part
+        .bytes()
+        .chain(frac_part.bytes())
+        .all(|b| b.is_ascii_digit())
+    {
+        return Err(parse_error("unexpected character"));
+    }
+    if frac_part.len() > decimals {
+        return Err(parse_error("too many fractional digits"));
+    }
+
+    let parse_digits = |digits: &str| {
+        if digits.is_empty() {
+            Ok(U256::ZERO)
+        } else {
+            U256::from_str_radix(digits, 10).map_err(|_| CodecError::Overflow)
+        }
+    };
+
+    let int_scaled =
+        checked_scale(parse_digits(int_part)?, decimals).ok_or(CodecError::Overflow)?;
+    let frac_scaled = checked_scale(parse_digits(frac_part)?, decimals - frac_part.len())
+        .ok_or(CodecError::Overflow)?;
+    int_scaled
+        .checked_add(frac_scaled)
+        .ok_or(CodecError::Overflow)
+}
+
+/// Formats the value scaled by `10^decimals` as a decimal string without trailing zeros.
+fn format_scaled(f: &mut fmt::Formatter<'_>, value: U256, decimals: usize) -> fmt::Result {
+    let (int_part, frac_part) = split_scaled(value, decimals);
+    write!(f, "{}", int_part)?;
+    if !frac_part.is_zero() {
+        let frac = format!("{:0>width$}", frac_part.to_string(), width = decimals);
+        write!(f, ".{}", frac.trim_end_matches('0'))?;
+    }
+    Ok(())
+}
+
+/// The `Uint` or `Signed` integer of `M` bits the scaled integer of a fixed-point number is
+/// encoded as, so fixed-point numbers are encoded exactly like Solidity's `uintM` and `intM`.
+trait ScaledInt<W>: Sized {
+    /// Truncates the full-width scaled integer to `M` bits.
+    fn from_wide(wide: W) -> Self;
+    /// Extends the value back to the full-width scaled integer.
+    fn into_wide(self) -> W;
+}
+
+impl<const BITS: usize, const LIMBS: usize> ScaledInt<U256> for Uint<BITS, LIMBS> {
+    fn from_wide(wide: U256) -> Self {
+        Self::from_le_slice(&wide.to_le_bytes::<32>()[..Self::BYTES])
+    }
+
+    fn into_wide(self) -> U256 {
+        U256::from_le_slice(&self.to_le_bytes_vec())
+    }
+}
+
+impl<const BITS: usize, const LIMBS: usize> ScaledInt<I256> for Signed<BITS, LIMBS> {
+    fn from_wide(wide: I256) -> Self {
+        Self::from_raw(Uint::from_wide(wide.into_raw()))
+    }
+
+    fn into_wide(self) -> I256 {
+        let fill = if self.is_negative() { 0xFF } else { 0x00 };
+        let mut word = [fill; 32];
+        word[..Self::BYTES].copy_from_slice(&self.into_raw().to_le_bytes_vec());
+        I256::from_raw(U256::from_le_bytes(word))
+    }
+}
+
+/// Evaluates `$body` with `$int` being the `Uint` (or `Signed`) type of `$bits` bits. `$bits` is
+/// a const generic, so only the matching arm survives monomorphization.
+macro_rules! with_scaled_int {
+    ($bits:expr, $signed:tt, $int:ident => $body:expr) => {
+        with_scaled_int!(
+            @arms $bits, $signed, $int, $body,
+            (8, 1), (16, 1), (24, 1), (32, 1), (40, 1), (48, 1), (56, 1), (64, 1),
+            (72, 2), (80, 2), (88, 2), (96, 2), (104, 2), (112, 2), (120, 2), (128, 2),
+            (136, 3), (144, 3), (152, 3), (160, 3), (168, 3), (176, 3), (184, 3), (192, 3),
+            (200, 4), (208, 4), (216, 4), (224, 4), (232, 4), (240, 4), (248, 4), (256, 4)
+        )
+    };
+    (@arms $bits:expr, $signed:tt, $int:ident, $body:expr, $(($b:literal, $l:literal)),+) => {
+        match $bits {
+            $($b => {
+                type $int = scaled_int_type!($signed, $b, $l);
+                $body
+            })+
+            _ => unreachable!("M is checked at compile time"),
+        }
+    };
+}
+
+macro_rules! scaled_int_type {
+    (true, $bits:literal, $limbs:literal) => {
+        Signed<$bits, $limbs>
+    };
+    (false, $bits:literal, $limbs:literal) => {
+        Uint<$bits, $limbs>
+    };
+}
+
+/// Implements the `Encoder` of a fixed-point type for one mode by delegating to the `Uint` or
+/// `Signed` encoder of the scaled integer.
+macro_rules! impl_fixed_encoder {
+    ($name:ident, $signed:tt, $sol_mode:literal) => {
+        impl<B: ByteOrder, const ALIGN: usize, const M: usize, const N: usize>
+            Encoder<B, { ALIGN }, $sol_mode> for $name<M, N>
+        {
+            const HEADER_SIZE: usize = if $sol_mode { 32 } else { Self::BYTES };
+            const IS_DYNAMIC: bool = false;
+
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                #[allow(clippy::let_unit_value)]
+                let () = Self::VALID;
+                with_scaled_int!(M, $signed, Int => {
+                    <Int as Encoder<B, ALIGN, $sol_mode>>::encode(
+                        &Int::from_wide(self.0),
+                        buf,
+                        offset,
+                    )
+                })
+            }
+
+            fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+                #[allow(clippy::let_unit_value)]
+                let () = Self::VALID;
+                with_scaled_int!(M, $signed, Int => {
+                    <Int as Encoder<B, ALIGN, $sol_mode>>::decode(buf, offset)
+                        .map(|value| Self(value.into_wide()))
+                })
+            }
+
+            fn partial_decode(
+                buf: &impl Buf,
+                offset: usize,
+            ) -> Result<(usize, usize), CodecError> {
+                #[allow(clippy::let_unit_value)]
+                let () = Self::VALID;
+                with_scaled_int!(M, $signed, Int => {
+                    <Int as Encoder<B, ALIGN, $sol_mode>>::partial_decode(buf, offset)
+                })
+            }
+        }
+    };
+}
+
+/// Implements a decimal fixed-point type with `M` bits and `N` decimals stored as the scaled
+/// integer, i.e. the value `v` is represented by `v * 10^N`.
+macro_rules! impl_fixed {
+    ($name:ident, $raw:ty, $signed:tt, $sol_prefix:expr, $doc:expr) => {
+        #[doc = $doc]
+        ///
+        /// Encoded as its scaled integer: a 32-byte word in Solidity mode and the minimal-width
+        /// `M / 8` bytes integer in Fluent mode.
+        ///
+        /// `M` must be a multiple of 8 in `8..=256` and `N` must be in `0..=80`, anything else
+        /// fails to compile:
+        ///
+        #[doc = concat!(
+            "```compile_fail\nlet _ = codec2::",
+            stringify!($name),
+            "::<7, 90>::sol_type_name();\n```"
+        )]
+        ///
+        /// The check covers encoding and decoding too, whose Solidity header doesn't depend on `M`:
+        ///
+        #[doc = concat!(
+            "```compile_fail\nlet _ = codec2::SolidityABI::<codec2::",
+            stringify!($name),
+            "<7, 2>>::decode(&&[0u8; 32][..], 0);\n```"
+        )]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name<const M: usize, const N: usize>($raw);
+
+        impl<const M: usize, const N: usize> $name<M, N> {
+            const VALID: () = assert!(
+                M >= 8 && M <= 256 && M % 8 == 0 && N <= 80,
+                "M must be a multiple of 8 in 8..=256 and N must be in 0..=80"
+            );
+
+            /// Size of the scaled integer in bytes.
+            pub const BYTES: usize = {
+                #[allow(clippy::let_unit_value)]
+                let () = Self::VALID;
+                M / 8
+            };
+            pub const ZERO: Self = {
+                #[allow(clippy::let_unit_value)]
+                let () = Self::VALID;
+                Self(<$raw>::ZERO)
+            };
+
+            /// Canonical Solidity type name, e.g. `ufixed128x18`.
+            pub fn sol_type_name() -> String {
+                #[allow(clippy::let_unit_value)]
+                let () = Self::VALID;
+                format!("{}{}x{}", $sol_prefix, M, N)
+            }
+
+            /// Creates a value from the scaled integer, returns `CodecError::Overflow` if it
+            /// doesn't fit into `M` bits.
+            pub fn from_raw(raw: $raw) -> Result<Self, CodecError> {
+                #[allow(clippy::let_unit_value)]
+                let () = Self::VALID;
+                with_scaled_int!(M, $signed, Int => {
+                    if Int::from_wide(raw).into_wide() == raw {
+                        Ok(Self(raw))
+                    } else {
+                        Err(CodecError::Overflow)
+                    }
+                })
+            }
+
+            /// Returns the scaled integer.
+            pub const fn into_raw(self) -> $raw {
+                self.0
+            }
+        }
+
+        impl<const M: usize, const N: usize> Default for $name<M, N> {
+            fn default() -> Self {
+                Self::ZERO
+            }
+        }
+
+        impl<const M: usize, const N: usize> fmt::Display for $name<M, N> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let (sign, abs) = fixed_sign_and_abs!($signed, self.0);
+                if sign == Sign::Negative {
+                    write!(f, "-")?;
+                }
+                format_scaled(f, abs, N)
+            }
+        }
+
+        impl<const M: usize, const N: usize> FromStr for $name<M, N> {
+            type Err = CodecError;
+
+            /// Parses decimal strings like `42`, `-1.5` or `.25`. The value is never rounded,
+            /// more than `N` fractional digits is an error.
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let (sign, abs) = match s.strip_prefix('-') {
+                    Some(abs) => (Sign::Negative, abs),
+                    None => (Sign::Positive, s),
+                };
+                if sign == Sign::Negative && !$signed {
+                    return Err(CodecError::Decoding(DecodingError::ParseError(format!(
+                        "invalid fixed-point number {:?}: unsigned value can't be negative",
+                        s
+                    ))));
+                }
+                let abs = parse_scaled(abs, N)?;
+                Self::from_raw(fixed_from_sign_and_abs!($signed, sign, abs)?)
+            }
+        }
+
+        impl<const M: usize, const N: usize> TryFrom<&str> for $name<M, N> {
+            type Error = CodecError;
+
+            fn try_from(value: &str) -> Result<Self, Self::Error> {
+                value.parse()
+            }
+        }
+
+        impl_fixed_encoder!($name, $signed, false);
+        impl_fixed_encoder!($name, $signed, true);
+
+        impl<const M: usize, const N: usize> PackedEncoder for $name<M, N> {
+            const PACKED_SIZE: Option<usize> = Some(Self::BYTES);
+            const PACKED_ELEMENT_SIZE: Option<usize> = Some(PACKED_WORD_SIZE);
+
+            fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+                with_scaled_int!(M, $signed, Int => Int::from_wide(self.0).encode_packed(buf))
+            }
+
+            fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+                with_scaled_int!(M, $signed, Int => {
+                    Int::from_wide(self.0).encode_packed_array_element(buf)
+                })
+            }
+        }
+    };
+}
+
+macro_rules! fixed_sign_and_abs {
+    (true, $value:expr) => {
+        $value.into_sign_and_abs()
+    };
+    (false, $value:expr) => {
+        (Sign::Positive, $value)
+    };
+}
+
+macro_rules! fixed_from_sign_and_abs {
+    (true, $sign:expr, $abs:expr) => {
+        I256::checked_from_sign_and_abs($sign, $abs).ok_or(CodecError::Overflow)
+    };
+    (false, $sign:expr, $abs:expr) => {
+        Ok::<_, CodecError>($abs)
+    };
+}
+
+impl_fixed!(
+    UFixed,
+    U256,
+    false,
+    "ufixed",
+    "Unsigned decimal fixed-point number, Solidity's `ufixedMxN`."
+);
+impl_fixed!(
+    Fixed,
+    I256,
+    true,
+    "fixed",
+    "Signed decimal fixed-point number, Solidity's `fixedMxN`."
+);
+
+impl<const M: usize, const N: usize> UFixed<M, N> {
+    /// Creates a value from an integer, returns `CodecError::Overflow` if it doesn't fit.
+    pub fn from_integer(value: U256) -> Result<Self, CodecError> {
+        Self::from_raw(checked_scale(value, N).ok_or(CodecError::Overflow)?)
+    }
+
+    /// Returns the integer part of the value.
+    pub fn trunc(&self) -> U256 {
+        split_scaled(self.0, N).0
+    }
+}
+
+impl<const M: usize, const N: usize> Fixed<M, N> {
+    /// Creates a value from an integer, returns `CodecError::Overflow` if it doesn't fit.
+    pub fn from_integer(value: I256) -> Result<Self, CodecError> {
+        let (sign, abs) = value.into_sign_and_abs();
+        let abs = checked_scale(abs, N).ok_or(CodecError::Overflow)?;
+        Self::from_raw(I256::checked_from_sign_and_abs(sign, abs).ok_or(CodecError::Overflow)?)
+    }
+
+    /// Returns the integer part of the value, rounded towards zero.
+    pub fn trunc(&self) -> I256 {
+        let (sign, abs) = self.0.into_sign_and_abs();
+        I256::checked_from_sign_and_abs(sign, split_scaled(abs, N).0)
+            .expect("integer part is smaller than the value")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::encoder::{FluentABI, SolidityABI};
+    use alloy_sol_types::{sol_data, SolType};
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_sol_type_name() {
+        assert_eq!(UFixed128x18::sol_type_name(), "ufixed128x18");
+        assert_eq!(Fixed::<64, 10>::sol_type_name(), "fixed64x10");
+    }
+
+    #[test]
+    fn test_string_conversions() {
+        let value: UFixed128x18 = "1.5".parse().unwrap();
+        assert_eq!(value.into_raw(), U256::from(15u64 * 10u64.pow(17)));
+        assert_eq!(value.to_string(), "1.5");
+        assert_eq!(value.trunc(), U256::from(1));
+
+        let value = Fixed::<64, 4>::try_from("-12.0034").unwrap();
+        assert_eq!(value.into_raw(), I256::try_from(-120034).unwrap());
+        assert_eq!(value.to_string(), "-12.0034");
+        assert_eq!(value.trunc(), I256::try_from(-12).unwrap());
+
+        assert_eq!(".25".parse::<UFixed<16, 2>>().unwrap().to_string(), "0.25");
+        assert_eq!("42".parse::<Fixed<16, 2>>().unwrap().to_string(), "42");
+
+        // Too many decimals, the value is never rounded
+        assert!("0.123".parse::<UFixed<16, 2>>().is_err());
+        // Unsigned values can't be negative
+        assert!("-1".parse::<UFixed<16, 2>>().is_err());
+        // Not a number
+        assert!("1.2.3".parse::<UFixed<16, 2>>().is_err());
+        assert!("".parse::<UFixed<16, 2>>().is_err());
+        assert!("0x10".parse::<UFixed<16, 2>>().is_err());
+        // 655.36 * 100 doesn't fit into 16 bits
+        assert!(matches!(
+            "655.36".parse::<UFixed<16, 2>>(),
+            Err(CodecError::Overflow)
+        ));
+        assert_eq!(
+            "655.35".parse::<UFixed<16, 2>>().unwrap().into_raw(),
+            U256::from(u16::MAX)
+        );
+        assert!("-1.29".parse::<Fixed<8, 2>>().is_err());
+        assert!("-1.28".parse::<Fixed<8, 2>>().is_ok());
+    }
+
+    #[test]
+    fn test_integer_conversions() {
+        assert_eq!(
+            UFixed::<16, 2>::from_integer(U256::from(7))
+                .unwrap()
+                .into_raw(),
+            U256::from(700)
+        );
+        assert!(UFixed::<16, 2>::from_integer(U256::from(656)).is_err());
+        assert_eq!(
+            Fixed::<16, 2>::from_integer(I256::MINUS_ONE)
+                .unwrap()
+                .into_raw(),
+            I256::try_from(-100).unwrap()
+        );
+        assert!(Fixed::<16, 2>::from_raw(I256::try_from(40000).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_fixed_sol() {
+        let value: UFixed128x18 = "3.14".parse().unwrap();
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&value, &mut buf, 0).unwrap();
+        assert_eq!(
+            buf.to_vec(),
+            sol_data::Uint::<128>::abi_encode(&(314u128 * 10u128.pow(16)))
+        );
+        assert_eq!(SolidityABI::<UFixed128x18>::decode(&buf, 0).unwrap(), value);
+
+        // Negative values are sign-extended to the full word
+        let value: Fixed<64, 2> = "-0.01".parse().unwrap();
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&value, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), sol_data::Int::<64>::abi_encode(&-1i64));
+        assert_eq!(SolidityABI::<Fixed<64, 2>>::decode(&buf, 0).unwrap(), value);
+    }
+
+    #[test]
+    fn test_fixed_sol_strict_decoding() {
+        // 2^128 doesn't fit into ufixed128x18
+        let mut word = [0u8; 32];
+        word[15] = 1;
+        assert!(SolidityABI::<UFixed128x18>::decode(&&word[..], 0).is_err());
+        assert!(SolidityABI::<UFixed<256, 18>>::decode(&&word[..], 0).is_ok());
+
+        // A positive int64 with a negative sign extension
+        let mut word = [0xFFu8; 32];
+        word[24] = 0x7F;
+        assert!(SolidityABI::<Fixed<64, 2>>::decode(&&word[..], 0).is_err());
+    }
+
+    #[test]
+    fn test_fixed_matches_scaled_int() {
+        let value: Fixed<72, 2> = "-1.5".parse().unwrap();
+        let scaled = Signed::<72, 2>::try_from(-150).unwrap();
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&value, &mut buf, 0).unwrap();
+        let mut expected = BytesMut::new();
+        FluentABI::encode(&scaled, &mut expected, 0).unwrap();
+        assert_eq!(buf, expected);
+        assert_eq!(FluentABI::<Fixed<72, 2>>::decode(&buf, 0).unwrap(), value);
+
+        let value: UFixed<200, 2> = "2.5".parse().unwrap();
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&value, &mut buf, 0).unwrap();
+        assert_eq!(
+            buf.to_vec(),
+            sol_data::Uint::<200>::abi_encode(&Uint::<200, 4>::from(250))
+        );
+        assert_eq!(
+            SolidityABI::<UFixed<200, 2>>::decode(&buf, 0).unwrap(),
+            value
+        );
+
+        let mut buf = BytesMut::new();
+        value.encode_packed(&mut buf).unwrap();
+        assert_eq!(buf.to_vec(), [&[0u8; 24][..], &[250]].concat());
+    }
+
+    #[test]
+    fn test_fixed_wasm_minimal_width() {
+        let value = (
+            UFixed::<64, 2>::from_str("1.01").unwrap(),
+            Fixed::<8, 1>::from_str("-0.1").unwrap(),
+        );
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&value, &mut buf, 0).unwrap();
+        assert_eq!(hex::encode(&buf), "6500000000000000ffffffff");
+
+        let decoded = FluentABI::<(UFixed<64, 2>, Fixed<8, 1>)>::decode(&buf, 0).unwrap();
+        assert_eq!(decoded, value);
+    }
+}
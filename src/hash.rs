@@ -1,124 +1,382 @@
 use crate::{
-    bytes::{read_bytes_header, write_bytes, write_bytes_solidity, write_bytes_wasm},
-    encoder::{align_up, read_u32_aligned, write_u32_aligned, Encoder},
+    bytes::read_bytes_header,
+    encoder::{
+        align_up,
+        append_elements,
+        contiguous,
+        encoded_tail_len,
+        ensure_buf_size,
+        read_u32_aligned,
+        write_u32_aligned,
+        Encoder,
+    },
     error::{CodecError, DecodingError},
+    primitive::element_header_size,
+    sink::EncodeSink,
 };
-use alloc::{format, string::ToString, vec::Vec};
-use byteorder::ByteOrder;
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::ToString,
+    vec::Vec,
+};
+use byteorder::{ByteOrder, BE};
 use bytes::{Buf, BytesMut};
-use core::{fmt::Debug, hash::Hash};
+use core::{
+    hash::{BuildHasher, Hash},
+    iter,
+    ops::{Deref, DerefMut, Range},
+};
 use hashbrown::{HashMap, HashSet};
 
-/// Implement encoding for HashMap, SOL_MODE = false
-impl<K, V, B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, false> for HashMap<K, V>
+/// Maps are encoded as a length followed by two arrays: keys and values. Entries are written in
+/// the order given by the caller, for hash maps it's the sorted order of keys, so equal maps
+/// always produce the same bytes no matter what collection type or hasher is used.
+///
+/// Fluent mode:
+/// - header: length, keys offset and size, values offset and size
+/// - body: encoded keys, encoded values
+///
+/// Solidity mode (see `nested_map.md`):
+/// - header: offset of the map data, like for any other dynamic type
+/// - body: length, keys offset, values offset (both relative to their own position), keys length
+///   and keys, values length and values
+///
+/// Keys and values are encoded as arrays, so dynamic keys and values have their offsets relative
+/// to the start of the array they belong to.
+const fn map_header_size<const SOL_MODE: bool>() -> usize {
+    if SOL_MODE {
+        32 // offset
+    } else {
+        4 + 8 + 8 // length + keys_header + values_header
+    }
+}
+
+/// Sets are encoded like maps without values.
+const fn set_header_size<const SOL_MODE: bool>() -> usize {
+    if SOL_MODE {
+        32 // offset
+    } else {
+        4 + 8 // length + data_header
+    }
+}
+
+/// Writes the number of elements at the end of the buffer, followed by the elements encoded one
+/// after another. Element offsets are relative to the first element.
+fn write_elements_solidity<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &mut impl EncodeSink,
+    elements: impl ExactSizeIterator<Item = &'a T>,
+) -> Result<(), CodecError>
 where
-    K: Default + Sized + Encoder<B, { ALIGN }, false> + Eq + Hash + Ord,
-    V: Default + Sized + Encoder<B, { ALIGN }, false>,
+    T: Encoder<B, { ALIGN }, { SOL_MODE }> + 'a,
 {
-    const HEADER_SIZE: usize = 4 + 8 + 8; // length + keys_header + values_header
-    const IS_DYNAMIC: bool = true;
+    write_u32_aligned::<B, ALIGN>(buf, buf.len(), elements.len() as u32)?;
+    append_elements::<T, B, ALIGN, SOL_MODE>(
+        buf,
+        elements,
+        element_header_size::<T, B, ALIGN, SOL_MODE>(),
+    )?;
+    Ok(())
+}
+
+/// Encodes the elements one after another at the end of the buffer and writes their offset and
+/// size at `offset`. Element offsets are relative to the first element.
+fn write_elements_wasm<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &mut impl EncodeSink,
+    offset: usize,
+    elements: impl ExactSizeIterator<Item = &'a T>,
+) -> Result<(), CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }> + 'a,
+{
+    ensure_buf_size(buf, offset + align_up::<ALIGN>(4) * 2)?;
+
+    let data_offset = append_elements::<T, B, ALIGN, SOL_MODE>(
+        buf,
+        elements,
+        element_header_size::<T, B, ALIGN, SOL_MODE>(),
+    )?;
+    write_u32_aligned::<B, ALIGN>(buf, offset, data_offset as u32)?;
+    write_u32_aligned::<B, ALIGN>(
+        buf,
+        offset + align_up::<ALIGN>(4),
+        (buf.len() - data_offset) as u32,
+    )
+}
+
+/// Returns the number of bytes [`append_elements`] writes for the elements.
+fn elements_len<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    elements: impl ExactSizeIterator<Item = &'a T>,
+) -> usize
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }> + 'a,
+{
+    element_header_size::<T, B, ALIGN, SOL_MODE>() * elements.len()
+        + elements
+            .map(encoded_tail_len::<T, B, ALIGN, SOL_MODE>)
+            .sum::<usize>()
+}
+
+/// Returns the number of bytes [`encode_map_entries`] writes for the given keys and values.
+fn map_encoded_len<'a, K, V, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    keys: impl ExactSizeIterator<Item = &'a K>,
+    values: impl ExactSizeIterator<Item = &'a V>,
+) -> usize
+where
+    K: Encoder<B, { ALIGN }, { SOL_MODE }> + 'a,
+    V: Encoder<B, { ALIGN }, { SOL_MODE }> + 'a,
+{
+    let data_len =
+        elements_len::<K, B, ALIGN, SOL_MODE>(keys) + elements_len::<V, B, ALIGN, SOL_MODE>(values);
+
+    if SOL_MODE {
+        // Offset, then length, keys offset and values offset, then the length of both arrays
+        map_header_size::<SOL_MODE>() + 96 + align_up::<ALIGN>(4) * 2 + data_len
+    } else {
+        align_up::<ALIGN>(map_header_size::<SOL_MODE>()) + data_len
+    }
+}
+
+/// Returns the number of bytes [`encode_set_entries`] writes for the given elements.
+fn set_encoded_len<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    elements: impl ExactSizeIterator<Item = &'a T>,
+) -> usize
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }> + 'a,
+{
+    let data_len = elements_len::<T, B, ALIGN, SOL_MODE>(elements);
+
+    if SOL_MODE {
+        // Offset, then length and data offset, then the length of the array
+        set_header_size::<SOL_MODE>() + 64 + align_up::<ALIGN>(4) + data_len
+    } else {
+        align_up::<ALIGN>(set_header_size::<SOL_MODE>()) + data_len
+    }
+}
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
+/// Writes the empty map, the zero value of all map types.
+fn encode_empty_map<K, V, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &mut impl EncodeSink,
+    offset: usize,
+) -> Result<(), CodecError>
+where
+    K: Encoder<B, { ALIGN }, { SOL_MODE }>,
+    V: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    encode_map_entries::<K, V, B, ALIGN, SOL_MODE>(&[], buf, offset)
+}
+
+/// Writes the empty set, the zero value of all set types.
+fn encode_empty_set<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &mut impl EncodeSink,
+    offset: usize,
+) -> Result<(), CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    encode_set_entries::<T, B, ALIGN, SOL_MODE>(&[], buf, offset)
+}
+
+/// Decodes `length` consecutive elements from the buffer.
+fn decode_elements<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &impl Buf,
+    length: usize,
+) -> Result<Vec<T>, CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    T::decode_slice(
+        buf,
+        0,
+        length,
+        element_header_size::<T, B, ALIGN, SOL_MODE>(),
+    )
+}
+
+/// Encodes map entries in the given order.
+fn encode_map_entries<K, V, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    entries: &[(&K, &V)],
+    buf: &mut impl EncodeSink,
+    offset: usize,
+) -> Result<(), CodecError>
+where
+    K: Encoder<B, { ALIGN }, { SOL_MODE }>,
+    V: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let keys = || entries.iter().map(|(k, _)| *k);
+    let values = || entries.iter().map(|(_, v)| *v);
+
+    if SOL_MODE {
+        ensure_buf_size(buf, offset + map_header_size::<SOL_MODE>())?;
+
+        // Write offset of the map data, it's appended to the end of the buffer
+        let start_offset = buf.len();
+        write_u32_aligned::<B, ALIGN>(buf, offset, start_offset as u32)?;
+
+        // Write map size
+        write_u32_aligned::<B, ALIGN>(buf, start_offset, entries.len() as u32)?;
+        ensure_buf_size(buf, start_offset + 96)?;
+
+        // Write key offset, key header and keys data
+        let relative_key_offset = buf.len() - start_offset - 32;
+        write_u32_aligned::<B, ALIGN>(buf, start_offset + 32, relative_key_offset as u32)?;
+        write_elements_solidity::<K, B, ALIGN, SOL_MODE>(buf, keys())?;
+
+        // Write values offset, values header and values data
+        let relative_value_offset = buf.len() - start_offset - 64;
+        write_u32_aligned::<B, ALIGN>(buf, start_offset + 64, relative_value_offset as u32)?;
+        write_elements_solidity::<V, B, ALIGN, SOL_MODE>(buf, values())?;
+    } else {
         let aligned_header_el_size = align_up::<ALIGN>(4);
-        let aligned_header_size = align_up::<ALIGN>(Self::HEADER_SIZE);
+        let aligned_header_size = align_up::<ALIGN>(map_header_size::<SOL_MODE>());
 
         // Ensure buf is large enough for the header
         if buf.len() < offset + aligned_header_size {
-            buf.resize(offset + aligned_header_size, 0);
+            buf.resize_zeroed(offset + aligned_header_size)?;
         }
 
         // Write map size
-        write_u32_aligned::<B, ALIGN>(buf, offset, self.len() as u32);
+        write_u32_aligned::<B, ALIGN>(buf, offset, entries.len() as u32)?;
 
-        // Make sure keys & values are sorted
-        let mut entries: Vec<_> = self.iter().collect();
-        entries.sort_by(|a, b| a.0.cmp(b.0));
+        // Write keys header and keys data
+        write_elements_wasm::<K, B, ALIGN, SOL_MODE>(buf, offset + aligned_header_el_size, keys())?;
 
-        // Encode and write keys
-        let mut key_buf = BytesMut::zeroed(align_up::<ALIGN>(K::HEADER_SIZE) * self.len());
+        // Write values header and values data
+        write_elements_wasm::<V, B, ALIGN, SOL_MODE>(
+            buf,
+            offset + aligned_header_el_size * 3,
+            values(),
+        )?;
+    }
 
-        for (i, (key, _)) in entries.iter().enumerate() {
-            let key_offset = align_up::<ALIGN>(K::HEADER_SIZE) * i;
-            key.encode(&mut key_buf, key_offset)?;
-        }
+    Ok(())
+}
 
-        // write keys header and keys data
-        write_bytes::<B, ALIGN, false>(
-            buf,
-            offset + aligned_header_el_size,
-            &key_buf,
-            entries.len() as u32,
-        );
+/// Reads the map header and returns the number of entries and the byte ranges of the encoded keys
+/// and values. Elements inside each range are encoded one after another.
+pub(crate) fn read_map_layout<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &impl Buf,
+    offset: usize,
+) -> Result<(usize, Range<usize>, Range<usize>), CodecError> {
+    let header_size = align_up::<ALIGN>(map_header_size::<SOL_MODE>());
+    let header_end = offset
+        .checked_add(header_size)
+        .ok_or(CodecError::Decoding(DecodingError::Overflow))?;
+
+    if buf.remaining() < header_end {
+        return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+            expected: header_end,
+            found: buf.remaining(),
+            msg: "Not enough data to decode map header".to_string(),
+        }));
+    }
 
-        // Encode and write values
-        let mut value_buf = BytesMut::zeroed(align_up::<ALIGN>(V::HEADER_SIZE) * self.len());
-        for (i, (_, value)) in entries.iter().enumerate() {
-            let value_offset = align_up::<ALIGN>(V::HEADER_SIZE) * i;
-            value.encode(&mut value_buf, value_offset)?;
-        }
+    if SOL_MODE {
+        const KEYS_OFFSET: usize = 32;
+        const VALUES_OFFSET: usize = 64;
 
-        write_bytes_wasm::<B, ALIGN>(buf, offset + aligned_header_el_size * 3, &value_buf);
+        // Read offset of the map data
+        let start_offset = read_u32_aligned::<B, ALIGN>(buf, offset)? as usize;
 
-        Ok(())
-    }
+        let length = read_u32_aligned::<B, ALIGN>(buf, start_offset)? as usize;
+        if length == 0 {
+            return Ok((0, 0..0, 0..0));
+        }
 
-    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
-        let aligned_header_el_size = align_up::<ALIGN>(4);
-        let aligned_header_size = align_up::<{ ALIGN }>(Self::HEADER_SIZE);
+        // Read relative keys and values offsets (relative to their own position)
+        let keys_offset = read_u32_aligned::<B, ALIGN>(buf, start_offset + KEYS_OFFSET)? as usize;
+        let values_offset =
+            read_u32_aligned::<B, ALIGN>(buf, start_offset + VALUES_OFFSET)? as usize;
 
-        if buf.remaining() < offset + aligned_header_size {
+        // Calculate absolute offsets, data starts after the length word
+        let keys_start = keys_offset
+            .checked_add(start_offset + KEYS_OFFSET + 32)
+            .ok_or(CodecError::Decoding(DecodingError::Overflow))?;
+        let values_start = values_offset
+            .checked_add(start_offset + VALUES_OFFSET + 32)
+            .ok_or(CodecError::Decoding(DecodingError::Overflow))?;
+
+        if buf.remaining() < keys_start.max(values_start) {
             return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
-                expected: offset + aligned_header_size,
+                expected: keys_start.max(values_start),
                 found: buf.remaining(),
-                msg: "Not enough data to decode HashMap header".to_string(),
+                msg: "Not enough data to decode map entries".to_string(),
             }));
         }
 
-        let length = read_u32_aligned::<B, { ALIGN }>(buf, offset)? as usize;
+        Ok((
+            length,
+            keys_start..buf.remaining(),
+            values_start..buf.remaining(),
+        ))
+    } else {
+        let aligned_header_el_size = align_up::<ALIGN>(4);
 
+        let length = read_u32_aligned::<B, ALIGN>(buf, offset)? as usize;
         let (keys_offset, keys_length) =
-            read_bytes_header::<B, { ALIGN }, false>(buf, offset + aligned_header_el_size)?;
-
+            read_bytes_header::<B, ALIGN, false>(buf, offset + aligned_header_el_size)?;
         let (values_offset, values_length) =
-            read_bytes_header::<B, { ALIGN }, false>(buf, offset + aligned_header_el_size * 3)?;
+            read_bytes_header::<B, ALIGN, false>(buf, offset + aligned_header_el_size * 3)?;
 
-        let key_bytes = &buf.chunk()[keys_offset..keys_offset + keys_length];
-        let value_bytes = &buf.chunk()[values_offset..values_offset + values_length];
+        if buf.remaining() < (keys_offset + keys_length).max(values_offset + values_length) {
+            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+                expected: (keys_offset + keys_length).max(values_offset + values_length),
+                found: buf.remaining(),
+                msg: "Not enough data to decode map entries".to_string(),
+            }));
+        }
 
-        let keys = (0..length).map(|i| {
-            let key_offset = align_up::<{ ALIGN }>(K::HEADER_SIZE) * i;
-            K::decode(&key_bytes, key_offset).unwrap_or_default()
-        });
+        Ok((
+            length,
+            keys_offset..keys_offset + keys_length,
+            values_offset..values_offset + values_length,
+        ))
+    }
+}
 
-        let values = (0..length).map(|i| {
-            let value_offset = align_up::<{ ALIGN }>(V::HEADER_SIZE) * i;
-            V::decode(&value_bytes, value_offset).unwrap_or_default()
-        });
+/// Decodes map entries in their encoded order.
+fn decode_map_entries<K, V, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &impl Buf,
+    offset: usize,
+) -> Result<Vec<(K, V)>, CodecError>
+where
+    K: Encoder<B, { ALIGN }, { SOL_MODE }>,
+    V: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let data = contiguous(buf)?;
+    let (length, keys_range, values_range) =
+        read_map_layout::<B, ALIGN, SOL_MODE>(&data.as_ref(), offset)?;
+    let keys_data = &data[keys_range];
+    let values_data = &data[values_range];
 
-        let result: HashMap<K, V> = keys.zip(values).collect();
+    let keys = decode_elements::<K, B, ALIGN, SOL_MODE>(&keys_data, length)?;
+    let values = decode_elements::<V, B, ALIGN, SOL_MODE>(&values_data, length)?;
 
-        if result.len() != length {
-            return Err(CodecError::Decoding(DecodingError::InvalidData(format!(
-                "Expected {} elements, but decoded {}",
-                length,
-                result.len()
-            ))));
-        }
+    Ok(keys.into_iter().zip(values).collect())
+}
 
-        Ok(result)
+/// Returns the offset and the size of the encoded keys and values.
+fn partial_decode_map<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &impl Buf,
+    offset: usize,
+) -> Result<(usize, usize), CodecError> {
+    let aligned_header_size = align_up::<ALIGN>(map_header_size::<SOL_MODE>());
+
+    if buf.remaining() < offset + aligned_header_size {
+        return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+            expected: offset + aligned_header_size,
+            found: buf.remaining(),
+            msg: "Not enough data to decode map header".to_string(),
+        }));
     }
 
-    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
-        let aligned_header_size = align_up::<ALIGN>(Self::HEADER_SIZE);
-
-        if buf.remaining() < offset + aligned_header_size {
-            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
-                expected: offset + aligned_header_size,
-                found: buf.remaining(),
-                msg: "Not enough data to decode HashMap header".to_string(),
-            }));
-        }
+    if SOL_MODE {
+        // Like for vectors, return the offset of the map data and the number of entries
+        let start_offset = read_u32_aligned::<B, ALIGN>(buf, offset)? as usize;
+        let length = read_u32_aligned::<B, ALIGN>(buf, start_offset)? as usize;
 
+        Ok((start_offset, length))
+    } else {
         let (keys_offset, keys_length) =
             read_bytes_header::<B, ALIGN, false>(buf, offset + align_up::<ALIGN>(4))?;
         let (_values_offset, values_length) =
@@ -127,380 +385,659 @@ where
         Ok((keys_offset, keys_length + values_length))
     }
 }
-/// Implement encoding for HashMap, SOL_MODE = true
-impl<K, V, B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, true> for HashMap<K, V>
+
+/// Encodes set elements in the given order.
+fn encode_set_entries<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    entries: &[&T],
+    buf: &mut impl EncodeSink,
+    offset: usize,
+) -> Result<(), CodecError>
 where
-    K: Debug + Default + Sized + Encoder<B, { ALIGN }, true> + Eq + Hash + Ord,
-    V: Debug + Default + Sized + Encoder<B, { ALIGN }, true>,
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
 {
-    const HEADER_SIZE: usize = 32 + 32 + 32 + 32; // offset + length + keys_header + values_header
+    let aligned_offset = align_up::<ALIGN>(offset);
 
-    const IS_DYNAMIC: bool = true;
+    if SOL_MODE {
+        ensure_buf_size(buf, aligned_offset + set_header_size::<SOL_MODE>())?;
+
+        // Write offset of the set data, it's appended to the end of the buffer
+        let start_offset = buf.len();
+        write_u32_aligned::<B, ALIGN>(buf, aligned_offset, start_offset as u32)?;
+
+        // Write set size
+        write_u32_aligned::<B, ALIGN>(buf, start_offset, entries.len() as u32)?;
+        ensure_buf_size(buf, start_offset + 64)?;
+
+        // Write data offset and values
+        let relative_data_offset = buf.len() - start_offset - 32;
+        write_u32_aligned::<B, ALIGN>(buf, start_offset + 32, relative_data_offset as u32)?;
+        write_elements_solidity::<T, B, ALIGN, SOL_MODE>(buf, entries.iter().copied())?;
+    } else {
+        let aligned_header_el_size = align_up::<ALIGN>(4);
+        let aligned_header_size = align_up::<ALIGN>(set_header_size::<SOL_MODE>());
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
         // Ensure buf is large enough for the header
-        if buf.len() < offset + Self::HEADER_SIZE {
-            buf.resize(offset + Self::HEADER_SIZE, 0);
+        if buf.len() < aligned_offset + aligned_header_size {
+            buf.resize_zeroed(aligned_offset + aligned_header_size)?;
         }
 
-        // Write offset size
-        write_u32_aligned::<B, ALIGN>(buf, offset, (32) as u32);
+        // Write set size
+        write_u32_aligned::<B, ALIGN>(buf, aligned_offset, entries.len() as u32)?;
 
-        // Write map size
-        write_u32_aligned::<B, ALIGN>(buf, offset + 32, self.len() as u32);
+        // Write values
+        write_elements_wasm::<T, B, ALIGN, SOL_MODE>(
+            buf,
+            aligned_offset + aligned_header_el_size,
+            entries.iter().copied(),
+        )?;
+    }
 
-        // Make sure keys & values are sorted
-        let mut entries: Vec<_> = self.iter().collect();
-        entries.sort_by(|a, b| a.0.cmp(b.0));
+    Ok(())
+}
 
-        // Encode and write keys
-        let mut key_buf = BytesMut::zeroed(align_up::<ALIGN>(K::HEADER_SIZE) * self.len());
+/// Reads the set header and returns the number of elements and the byte range of the encoded
+/// elements.
+pub(crate) fn read_set_layout<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &impl Buf,
+    offset: usize,
+) -> Result<(usize, Range<usize>), CodecError> {
+    let aligned_offset = align_up::<ALIGN>(offset);
+    let header_size = align_up::<ALIGN>(set_header_size::<SOL_MODE>());
+    let header_end = aligned_offset
+        .checked_add(header_size)
+        .ok_or(CodecError::Decoding(DecodingError::Overflow))?;
+
+    if buf.remaining() < header_end {
+        return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+            expected: header_end,
+            found: buf.remaining(),
+            msg: "Not enough data to decode set header".to_string(),
+        }));
+    }
 
-        for (i, (key, _)) in entries.iter().enumerate() {
-            let key_offset = align_up::<ALIGN>(K::HEADER_SIZE) * i;
-            key.encode(&mut key_buf, key_offset)?;
-        }
-        let relative_key_offset = buf.len() - offset - 64;
-        // Write key offset
-        write_u32_aligned::<B, ALIGN>(buf, offset + 64, relative_key_offset as u32);
-
-        // write key header and keys data to the buf
-        write_bytes_solidity::<B, ALIGN>(buf, offset + 64, &key_buf, entries.len() as u32);
-
-        // Write values offset
-        let relative_value_offset = buf.len() - offset - 96;
-        write_u32_aligned::<B, ALIGN>(buf, offset + 96, relative_value_offset as u32);
-
-        // Encode and write values
-        let mut value_buf = BytesMut::zeroed(align_up::<ALIGN>(V::HEADER_SIZE) * self.len());
-        for (i, (_, value)) in entries.iter().enumerate() {
-            let value_offset = align_up::<ALIGN>(V::HEADER_SIZE) * i;
-            value.encode(&mut value_buf, value_offset)?;
-        }
+    if SOL_MODE {
+        const DATA_OFFSET: usize = 32;
 
-        write_bytes_solidity::<B, ALIGN>(buf, buf.len(), &value_buf, entries.len() as u32);
+        // Read offset of the set data
+        let start_offset = read_u32_aligned::<B, ALIGN>(buf, aligned_offset)? as usize;
 
-        Ok(())
-    }
+        let length = read_u32_aligned::<B, ALIGN>(buf, start_offset)? as usize;
+        if length == 0 {
+            return Ok((0, 0..0));
+        }
 
-    // current solidity decode nested map
-    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
-        const KEYS_OFFSET: usize = 32;
-        const VALUES_OFFSET: usize = 64;
+        // Read relative data offset (relative to its own position), data starts after the
+        // length word
+        let values_offset = read_u32_aligned::<B, ALIGN>(buf, start_offset + DATA_OFFSET)? as usize;
+        let values_start = values_offset
+            .checked_add(start_offset + DATA_OFFSET + 32)
+            .ok_or(CodecError::Decoding(DecodingError::Overflow))?;
 
-        // Check if there's enough data to read the header
-        let header_end = offset
-            .checked_add(Self::HEADER_SIZE)
-            .ok_or_else(|| CodecError::Decoding(DecodingError::Overflow))?;
+        if buf.remaining() < values_start {
+            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+                expected: values_start,
+                found: buf.remaining(),
+                msg: "Not enough data to decode set entries".to_string(),
+            }));
+        }
 
-        if buf.remaining()
-            < usize::try_from(header_end)
-                .map_err(|_| CodecError::Decoding(DecodingError::Overflow))?
-        {
+        Ok((length, values_start..buf.remaining()))
+    } else {
+        let length = read_u32_aligned::<B, ALIGN>(buf, aligned_offset)? as usize;
+        let (data_offset, data_length) =
+            read_bytes_header::<B, ALIGN, false>(buf, aligned_offset + align_up::<ALIGN>(4))?;
+
+        if buf.remaining() < data_offset + data_length {
             return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
-                expected: header_end as usize,
+                expected: data_offset + data_length,
                 found: buf.remaining(),
-                msg: "Not enough data to decode HashMap header".to_string(),
+                msg: "Not enough data to decode set entries".to_string(),
             }));
         }
 
-        // Read data offset
-        let data_offset = read_u32_aligned::<B, { ALIGN }>(buf, offset)? as usize;
+        Ok((length, data_offset..data_offset + data_length))
+    }
+}
+
+/// Decodes set elements in their encoded order.
+fn decode_set_entries<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &impl Buf,
+    offset: usize,
+) -> Result<Vec<T>, CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let data = contiguous(buf)?;
+    let (length, values_range) = read_set_layout::<B, ALIGN, SOL_MODE>(&data.as_ref(), offset)?;
+    let values_data = &data[values_range];
 
-        // Calculate start offset
-        let start_offset = offset
-            .checked_add(data_offset)
-            .ok_or_else(|| CodecError::Decoding(DecodingError::Overflow))?;
+    decode_elements::<T, B, ALIGN, SOL_MODE>(&values_data, length)
+}
 
-        // Read length
-        let length = read_u32_aligned::<B, { ALIGN }>(buf, start_offset)? as usize;
-        if length == 0 {
-            return Ok(HashMap::new());
+/// Returns the offset and the size of the encoded set elements.
+fn partial_decode_set<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &impl Buf,
+    offset: usize,
+) -> Result<(usize, usize), CodecError> {
+    let aligned_offset = align_up::<ALIGN>(offset);
+    let aligned_header_size = align_up::<ALIGN>(set_header_size::<SOL_MODE>());
+
+    if buf.remaining() < aligned_offset + aligned_header_size {
+        return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+            expected: aligned_offset + aligned_header_size,
+            found: buf.remaining(),
+            msg: "Not enough data to decode set header".to_string(),
+        }));
+    }
+
+    if SOL_MODE {
+        // Like for vectors, return the offset of the set data and the number of elements
+        let start_offset = read_u32_aligned::<B, ALIGN>(buf, aligned_offset)? as usize;
+        let length = read_u32_aligned::<B, ALIGN>(buf, start_offset)? as usize;
+
+        Ok((start_offset, length))
+    } else {
+        read_bytes_header::<B, ALIGN, false>(buf, aligned_offset + align_up::<ALIGN>(4))
+    }
+}
+
+/// Checks that decoding didn't lose entries because of duplicate keys.
+fn check_decoded_len(expected: usize, found: usize) -> Result<(), CodecError> {
+    if expected != found {
+        return Err(CodecError::Decoding(DecodingError::InvalidData(format!(
+            "Expected {} elements, but decoded {}",
+            expected, found
+        ))));
+    }
+    Ok(())
+}
+
+/// Implements `Encoder` for a map type. `$entries` turns the map iterator into the list of entries
+/// in the order they are encoded.
+macro_rules! impl_map_encoder {
+    ($map:ident<K, V $(, $S:ident)?>, [$($key_bounds:tt)*], $entries:ident) => {
+        impl<K, V, $($S,)? B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+            Encoder<B, { ALIGN }, { SOL_MODE }> for $map<K, V $(, $S)?>
+        where
+            K: Sized + Encoder<B, { ALIGN }, { SOL_MODE }> + $($key_bounds)*,
+            V: Sized + Encoder<B, { ALIGN }, { SOL_MODE }>,
+            $($S: BuildHasher + Default,)?
+        {
+            const HEADER_SIZE: usize = map_header_size::<SOL_MODE>();
+            const IS_DYNAMIC: bool = true;
+
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                let entries = $entries(self.iter());
+                encode_map_entries::<K, V, B, ALIGN, SOL_MODE>(&entries, buf, offset)
+            }
+
+            fn encoded_len(&self) -> usize {
+                map_encoded_len::<K, V, B, ALIGN, SOL_MODE>(self.keys(), self.values())
+            }
+
+            fn encode_zero(buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                encode_empty_map::<K, V, B, ALIGN, SOL_MODE>(buf, offset)
+            }
+
+            fn zero_encoded_len() -> usize {
+                map_encoded_len::<K, V, B, ALIGN, SOL_MODE>(iter::empty(), iter::empty())
+            }
+
+            fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+                let entries = decode_map_entries::<K, V, B, ALIGN, SOL_MODE>(buf, offset)?;
+                let length = entries.len();
+                let result: Self = entries.into_iter().collect();
+                check_decoded_len(length, result.len())?;
+                Ok(result)
+            }
+
+            fn partial_decode(
+                buf: &impl Buf,
+                offset: usize,
+            ) -> Result<(usize, usize), CodecError> {
+                partial_decode_map::<B, ALIGN, SOL_MODE>(buf, offset)
+            }
         }
+    };
+}
 
-        // Read relative keys and values offsets (relative to the current offset)
-        let keys_offset =
-            read_u32_aligned::<B, { ALIGN }>(buf, start_offset + KEYS_OFFSET)? as usize;
-        let values_offset =
-            read_u32_aligned::<B, { ALIGN }>(buf, start_offset + VALUES_OFFSET)? as usize;
+/// Implements `Encoder` for a set type. `$entries` turns the set iterator into the list of
+/// elements in the order they are encoded.
+macro_rules! impl_set_encoder {
+    ($set:ident<T $(, $S:ident)?>, [$($bounds:tt)*], $entries:ident) => {
+        impl<T, $($S,)? B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+            Encoder<B, { ALIGN }, { SOL_MODE }> for $set<T $(, $S)?>
+        where
+            T: Sized + Encoder<B, { ALIGN }, { SOL_MODE }> + $($bounds)*,
+            $($S: BuildHasher + Default,)?
+        {
+            const HEADER_SIZE: usize = set_header_size::<SOL_MODE>();
+            const IS_DYNAMIC: bool = true;
 
-        // Calculate absolute offsets
-        let keys_start = keys_offset
-            .checked_add(start_offset)
-            .and_then(|sum| sum.checked_add(KEYS_OFFSET))
-            .ok_or_else(|| CodecError::Decoding(DecodingError::Overflow))?;
-        let values_start = values_offset
-            .checked_add(start_offset)
-            .and_then(|sum| sum.checked_add(VALUES_OFFSET))
-            .ok_or_else(|| CodecError::Decoding(DecodingError::Overflow))?;
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                let entries = $entries(self.iter());
+                encode_set_entries::<T, B, ALIGN, SOL_MODE>(&entries, buf, offset)
+            }
 
-        let mut result = HashMap::with_capacity(length);
+            fn encoded_len(&self) -> usize {
+                set_encoded_len::<T, B, ALIGN, SOL_MODE>(self.iter())
+            }
 
-        let keys_data = &buf.chunk()[keys_start + 32..];
-        let values_data = &buf.chunk()[values_start + 32..];
+            fn encode_zero(buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                encode_empty_set::<T, B, ALIGN, SOL_MODE>(buf, offset)
+            }
 
-        for i in 0..length {
-            let key_offset = align_up::<{ ALIGN }>(K::HEADER_SIZE)
-                .checked_mul(i)
-                .ok_or_else(|| CodecError::Decoding(DecodingError::Overflow))?;
-            let value_offset = align_up::<{ ALIGN }>(V::HEADER_SIZE)
-                .checked_mul(i)
-                .ok_or_else(|| CodecError::Decoding(DecodingError::Overflow))?;
+            fn zero_encoded_len() -> usize {
+                set_encoded_len::<T, B, ALIGN, SOL_MODE>(iter::empty())
+            }
 
-            let key = K::decode(&keys_data, key_offset)?;
-            let value = V::decode(&values_data, value_offset)?;
+            fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+                let entries = decode_set_entries::<T, B, ALIGN, SOL_MODE>(buf, offset)?;
+                let length = entries.len();
+                let result: Self = entries.into_iter().collect();
+                check_decoded_len(length, result.len())?;
+                Ok(result)
+            }
 
-            result.insert(key, value);
+            fn partial_decode(
+                buf: &impl Buf,
+                offset: usize,
+            ) -> Result<(usize, usize), CodecError> {
+                partial_decode_set::<B, ALIGN, SOL_MODE>(buf, offset)
+            }
         }
+    };
+}
+
+/// Hash maps have no order, so entries are sorted by key.
+fn sorted_map_entries<'a, K: Ord + 'a, V: 'a>(
+    iter: impl Iterator<Item = (&'a K, &'a V)>,
+) -> Vec<(&'a K, &'a V)> {
+    let mut entries: Vec<_> = iter.collect();
+    entries.sort_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+/// Hash sets have no order, so elements are sorted.
+fn sorted_set_entries<'a, T: Ord + 'a>(iter: impl Iterator<Item = &'a T>) -> Vec<&'a T> {
+    let mut entries: Vec<_> = iter.collect();
+    entries.sort();
+    entries
+}
 
-        Ok(result)
+/// B-tree collections already iterate in sorted order.
+fn ordered_entries<I: Iterator>(iter: I) -> Vec<I::Item> {
+    iter.collect()
+}
+
+impl_map_encoder!(HashMap<K, V, S>, [Eq + Hash + Ord], sorted_map_entries);
+impl_set_encoder!(HashSet<T, S>, [Eq + Hash + Ord], sorted_set_entries);
+impl_map_encoder!(BTreeMap<K, V>, [Ord], ordered_entries);
+impl_set_encoder!(BTreeSet<T>, [Ord], ordered_entries);
+
+/// A hash map or set whose entries are encoded in the order of their encoded keys instead of
+/// their `Ord` order.
+///
+/// Every key is encoded on its own (as a top-level value in the current mode) and entries are
+/// sorted by these bytes. This order doesn't depend on Rust types, so implementations in other
+/// languages can reproduce it, and keys don't have to implement `Ord`. Since entries aren't in
+/// `Ord` order, look them up with [`ByteOrderedMapView`](crate::ByteOrderedMapView) and
+/// [`ByteOrderedSetView`](crate::ByteOrderedSetView) rather than `MapView` and `SetView`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ByteOrdered<T>(pub T);
+
+impl<T> ByteOrdered<T> {
+    pub fn into_inner(self) -> T {
+        self.0
     }
+}
 
-    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
-        let aligned_header_size = align_up::<ALIGN>(Self::HEADER_SIZE);
+impl<T> Deref for ByteOrdered<T> {
+    type Target = T;
 
-        if buf.remaining() < offset + aligned_header_size {
-            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
-                expected: offset + aligned_header_size,
-                found: buf.remaining(),
-                msg: "Not enough data to decode HashMap header".to_string(),
-            }));
-        }
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
 
-        let (keys_offset, keys_length) =
-            read_bytes_header::<B, ALIGN, false>(buf, offset + align_up::<ALIGN>(4))?;
-        let (_values_offset, values_length) =
-            read_bytes_header::<B, ALIGN, false>(buf, offset + align_up::<ALIGN>(12))?;
+impl<T> DerefMut for ByteOrdered<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
 
-        Ok((keys_offset, keys_length + values_length))
+impl<T> From<T> for ByteOrdered<T> {
+    fn from(value: T) -> Self {
+        Self(value)
     }
 }
 
-/// Implement encoding for HashSet, SOL_MODE = false
-impl<T, B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, false> for HashSet<T>
+/// Encodes a key on its own, its bytes give the order of `ByteOrdered` entries.
+pub(crate) fn encode_key<K, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    key: &K,
+) -> Result<BytesMut, CodecError>
 where
-    T: Default + Sized + Encoder<B, { ALIGN }, false> + Eq + Hash + Ord,
+    K: Encoder<B, { ALIGN }, { SOL_MODE }>,
 {
-    const HEADER_SIZE: usize = 4 + 8; // length + data_header
-    const IS_DYNAMIC: bool = true;
+    let mut buf = BytesMut::new();
+    key.encode(&mut buf, 0)?;
+    Ok(buf)
+}
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
-        let aligned_offset = align_up::<ALIGN>(offset);
-        let aligned_header_el_size = align_up::<ALIGN>(4);
-        let aligned_header_size = align_up::<ALIGN>(Self::HEADER_SIZE);
+/// Sorts entries by the encoded bytes of their keys.
+fn sort_by_encoded_key<E, K, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    entries: impl Iterator<Item = E>,
+    key: impl Fn(&E) -> &K,
+) -> Result<Vec<E>, CodecError>
+where
+    K: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let mut encoded_entries = entries
+        .map(|entry| Ok((encode_key::<K, B, ALIGN, SOL_MODE>(key(&entry))?, entry)))
+        .collect::<Result<Vec<_>, CodecError>>()?;
+    encoded_entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(encoded_entries
+        .into_iter()
+        .map(|(_, entry)| entry)
+        .collect())
+}
 
-        // Ensure buf is large enough for the header
-        if buf.len() < aligned_offset + aligned_header_size {
-            buf.resize(aligned_offset + aligned_header_size, 0);
-        }
+/// Implements `Encoder` for `ByteOrdered` hash maps and sets.
+macro_rules! impl_byte_ordered_encoder {
+    ($map:ident, $set:ident) => {
+        impl<K, V, S, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+            Encoder<B, { ALIGN }, { SOL_MODE }> for ByteOrdered<$map<K, V, S>>
+        where
+            K: Sized + Encoder<B, { ALIGN }, { SOL_MODE }> + Eq + Hash,
+            V: Sized + Encoder<B, { ALIGN }, { SOL_MODE }>,
+            S: BuildHasher + Default,
+        {
+            const HEADER_SIZE: usize = map_header_size::<SOL_MODE>();
+            const IS_DYNAMIC: bool = true;
+
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                let entries =
+                    sort_by_encoded_key::<_, K, B, ALIGN, SOL_MODE>(self.0.iter(), |(key, _)| {
+                        *key
+                    })?;
+                encode_map_entries::<K, V, B, ALIGN, SOL_MODE>(&entries, buf, offset)
+            }
 
-        // Write set size
-        write_u32_aligned::<B, ALIGN>(buf, aligned_offset, self.len() as u32);
+            fn encoded_len(&self) -> usize {
+                map_encoded_len::<K, V, B, ALIGN, SOL_MODE>(self.0.keys(), self.0.values())
+            }
 
-        // Make sure a set is sorted
-        let mut entries: Vec<_> = self.iter().collect();
-        entries.sort();
+            fn encode_zero(buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                encode_empty_map::<K, V, B, ALIGN, SOL_MODE>(buf, offset)
+            }
+
+            fn zero_encoded_len() -> usize {
+                map_encoded_len::<K, V, B, ALIGN, SOL_MODE>(iter::empty(), iter::empty())
+            }
 
-        // Encode values
-        let mut value_buf = BytesMut::zeroed(align_up::<ALIGN>(T::HEADER_SIZE) * self.len());
-        for (i, value) in entries.iter().enumerate() {
-            let value_offset = align_up::<ALIGN>(T::HEADER_SIZE) * i;
-            value.encode(&mut value_buf, value_offset)?;
+            fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+                let entries = decode_map_entries::<K, V, B, ALIGN, SOL_MODE>(buf, offset)?;
+                let length = entries.len();
+                let result: $map<K, V, S> = entries.into_iter().collect();
+                check_decoded_len(length, result.len())?;
+                Ok(Self(result))
+            }
+
+            fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+                partial_decode_map::<B, ALIGN, SOL_MODE>(buf, offset)
+            }
         }
 
-        // Write values
-        write_bytes::<B, ALIGN, false>(
-            buf,
-            aligned_offset + aligned_header_el_size,
-            &value_buf,
-            entries.len() as u32,
-        );
+        impl<T, S, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+            Encoder<B, { ALIGN }, { SOL_MODE }> for ByteOrdered<$set<T, S>>
+        where
+            T: Sized + Encoder<B, { ALIGN }, { SOL_MODE }> + Eq + Hash,
+            S: BuildHasher + Default,
+        {
+            const HEADER_SIZE: usize = set_header_size::<SOL_MODE>();
+            const IS_DYNAMIC: bool = true;
 
-        Ok(())
-    }
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                let entries =
+                    sort_by_encoded_key::<_, T, B, ALIGN, SOL_MODE>(self.0.iter(), |value| value)?;
+                encode_set_entries::<T, B, ALIGN, SOL_MODE>(&entries, buf, offset)
+            }
 
-    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
-        let aligned_offset = align_up::<ALIGN>(offset);
-        let aligned_header_size = align_up::<ALIGN>(Self::HEADER_SIZE);
+            fn encoded_len(&self) -> usize {
+                set_encoded_len::<T, B, ALIGN, SOL_MODE>(self.0.iter())
+            }
 
-        if buf.remaining() < aligned_offset + aligned_header_size {
-            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
-                expected: aligned_offset + aligned_header_size,
-                found: buf.remaining(),
-                msg: "Not enough data to decode HashSet header".to_string(),
-            }));
+            fn encode_zero(buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                encode_empty_set::<T, B, ALIGN, SOL_MODE>(buf, offset)
+            }
+
+            fn zero_encoded_len() -> usize {
+                set_encoded_len::<T, B, ALIGN, SOL_MODE>(iter::empty())
+            }
+
+            fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+                let entries = decode_set_entries::<T, B, ALIGN, SOL_MODE>(buf, offset)?;
+                let length = entries.len();
+                let result: $set<T, S> = entries.into_iter().collect();
+                check_decoded_len(length, result.len())?;
+                Ok(Self(result))
+            }
+
+            fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+                partial_decode_set::<B, ALIGN, SOL_MODE>(buf, offset)
+            }
         }
+    };
+}
 
-        let length = read_u32_aligned::<B, ALIGN>(buf, aligned_offset)? as usize;
+impl_byte_ordered_encoder!(HashMap, HashSet);
 
-        let (data_offset, data_length) =
-            read_bytes_header::<B, ALIGN, false>(buf, aligned_offset + align_up::<ALIGN>(4))?;
+#[cfg(feature = "std")]
+mod std_impl {
+    use super::*;
+    use std::collections::{HashMap as StdHashMap, HashSet as StdHashSet};
 
-        let mut result = HashSet::with_capacity(length);
+    impl_map_encoder!(StdHashMap<K, V, S>, [Eq + Hash + Ord], sorted_map_entries);
+    impl_set_encoder!(StdHashSet<T, S>, [Eq + Hash + Ord], sorted_set_entries);
+    impl_byte_ordered_encoder!(StdHashMap, StdHashSet);
+}
 
-        let value_bytes = &buf.chunk()[data_offset..data_offset + data_length];
+/// Index maps keep their insertion order, decoding restores the encoded order.
+#[cfg(feature = "indexmap")]
+mod indexmap_impl {
+    use super::*;
+    use indexmap::{IndexMap, IndexSet};
 
-        for i in 0..length {
-            let value_offset = align_up::<ALIGN>(T::HEADER_SIZE) * i;
-            let value = T::decode(&value_bytes, value_offset)?;
-            result.insert(value);
-        }
+    impl_map_encoder!(IndexMap<K, V, S>, [Eq + Hash], ordered_entries);
+    impl_set_encoder!(IndexSet<T, S>, [Eq + Hash], ordered_entries);
+}
 
-        if result.len() != length {
-            return Err(CodecError::Decoding(DecodingError::InvalidData(format!(
-                "Expected {} elements, but decoded {}",
-                length,
-                result.len()
-            ))));
-        }
+/// A map that is encoded as the `(K[] keys, V[] values)` tuple in Solidity mode, with entries
+/// sorted by key.
+///
+/// As a value (`SolidityABI::encode` or a field) it's `abi.encode(map)` of a
+/// `struct Map { K[] keys; V[] values; }`, that EVM contracts decode with
+/// `abi.decode(data, (Map))`. [`SolidityMap::encode_params`] gives `abi.encode(keys, values)`
+/// instead, without the leading offset, decoded with `abi.decode(data, (K[], V[]))`.
+///
+/// In Fluent mode the encoding is the same as for `BTreeMap`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SolidityMap<K, V>(pub BTreeMap<K, V>);
+
+impl<K, V> SolidityMap<K, V> {
+    pub fn new() -> Self {
+        Self(BTreeMap::new())
+    }
 
-        Ok(result)
+    pub fn into_inner(self) -> BTreeMap<K, V> {
+        self.0
     }
+}
 
-    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
-        let aligned_offset = align_up::<ALIGN>(offset);
-        let aligned_header_size = align_up::<ALIGN>(Self::HEADER_SIZE);
+impl<K, V> Deref for SolidityMap<K, V> {
+    type Target = BTreeMap<K, V>;
 
-        if buf.remaining() < aligned_offset + aligned_header_size {
-            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
-                expected: aligned_offset + aligned_header_size,
-                found: buf.remaining(),
-                msg: "Not enough data to decode HashSet header".to_string(),
-            }));
-        }
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
 
-        let (data_offset, data_length) =
-            read_bytes_header::<B, ALIGN, false>(buf, aligned_offset + align_up::<ALIGN>(4))?;
+impl<K, V> DerefMut for SolidityMap<K, V> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
 
-        Ok((data_offset, data_length))
+impl<K, V> From<BTreeMap<K, V>> for SolidityMap<K, V> {
+    fn from(map: BTreeMap<K, V>) -> Self {
+        Self(map)
     }
 }
 
-/// Implement encoding for HashSet, SOL_MODE = true
-impl<T, B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, true> for HashSet<T>
+impl<K, V> SolidityMap<K, V>
 where
-    T: Debug + Default + Sized + Encoder<B, { ALIGN }, true> + Eq + Hash + Ord,
+    K: Encoder<BE, 32, true> + Ord,
+    V: Encoder<BE, 32, true>,
 {
-    const HEADER_SIZE: usize = 32 + 32 + 32; // offset + length + data_header
+    /// Appends `abi.encode(keys, values)`, the Solidity tuple without the offset pointing to it.
+    pub fn encode_params(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        encode_solidity_map_tuple::<K, V, BE, 32, true>(&self.0, buf)
+    }
+
+    /// Decodes the output of [`SolidityMap::encode_params`] starting at `offset`.
+    pub fn decode_params(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+        decode_solidity_map_tuple::<K, V, BE, 32, true>(&contiguous(buf)?, offset).map(Self)
+    }
+}
+
+impl<K: Ord, V> FromIterator<(K, V)> for SolidityMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<K, V, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    Encoder<B, { ALIGN }, { SOL_MODE }> for SolidityMap<K, V>
+where
+    K: Sized + Encoder<B, { ALIGN }, { SOL_MODE }> + Ord,
+    V: Sized + Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    const HEADER_SIZE: usize = map_header_size::<SOL_MODE>();
     const IS_DYNAMIC: bool = true;
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
-        let aligned_offset = align_up::<ALIGN>(offset);
 
-        // Ensure buf is large enough for the header
-        if buf.len() < aligned_offset + Self::HEADER_SIZE {
-            buf.resize(aligned_offset + Self::HEADER_SIZE, 0);
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        if !SOL_MODE {
+            return self.0.encode(buf, offset);
         }
 
-        // Write offset size
-        write_u32_aligned::<B, ALIGN>(buf, aligned_offset, 32 as u32);
+        ensure_buf_size(buf, offset + 32)?;
 
-        // Write set size
-        write_u32_aligned::<B, ALIGN>(buf, aligned_offset + 32, self.len() as u32);
-
-        // Make sure set is sorted
-        let mut entries: Vec<_> = self.iter().collect();
-        entries.sort();
+        // Write offset of the (keys, values) tuple
+        write_u32_aligned::<B, ALIGN>(buf, offset, buf.len() as u32)?;
+        encode_solidity_map_tuple::<K, V, B, ALIGN, SOL_MODE>(&self.0, buf)
+    }
 
-        // Encode values
-        let mut value_buf = BytesMut::zeroed(align_up::<ALIGN>(T::HEADER_SIZE) * self.len());
-        for (i, value) in entries.iter().enumerate() {
-            let value_offset = align_up::<ALIGN>(T::HEADER_SIZE) * i;
-            value.encode(&mut value_buf, value_offset)?;
+    fn encoded_len(&self) -> usize {
+        if !SOL_MODE {
+            return self.0.encoded_len();
         }
 
-        // Write data offset
-        let relative_data_offset = buf.len() - aligned_offset - 64;
-        write_u32_aligned::<B, ALIGN>(buf, aligned_offset + 64, relative_data_offset as u32);
+        // Offset, then the offsets of both arrays, then the arrays
+        32 + 64
+            + align_up::<ALIGN>(4) * 2
+            + elements_len::<K, B, ALIGN, SOL_MODE>(self.0.keys())
+            + elements_len::<V, B, ALIGN, SOL_MODE>(self.0.values())
+    }
 
-        // Write values
-        write_bytes_solidity::<B, ALIGN>(buf, buf.len(), &value_buf, entries.len() as u32);
+    /// The zero value is the empty map, in Solidity mode a tuple of two empty arrays.
+    fn encode_zero(buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        Self::new().encode(buf, offset)
+    }
 
-        Ok(())
+    fn zero_encoded_len() -> usize {
+        Self::new().encoded_len()
     }
 
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
-        const DATA_OFFSET: usize = 32;
-
-        let aligned_offset = align_up::<{ ALIGN }>(offset);
-
-        // Check if there's enough data to read the header
-        let header_end = aligned_offset
-            .checked_add(Self::HEADER_SIZE)
-            .ok_or_else(|| CodecError::Decoding(DecodingError::Overflow))?;
-
-        if buf.remaining()
-            < usize::try_from(header_end)
-                .map_err(|_| CodecError::Decoding(DecodingError::Overflow))?
-        {
-            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
-                expected: header_end as usize,
-                found: buf.remaining(),
-                msg: "Not enough data to decode HashSet header".to_string(),
-            }));
+        if !SOL_MODE {
+            return BTreeMap::decode(buf, offset).map(Self);
         }
 
-        // Read data offset
-        let data_offset = read_u32_aligned::<B, { ALIGN }>(buf, aligned_offset)? as usize;
-
-        // Calculate start offset
-        let start_offset = aligned_offset
-            .checked_add(data_offset)
-            .ok_or_else(|| CodecError::Decoding(DecodingError::Overflow))?;
+        let data = contiguous(buf)?;
+        let start_offset = read_u32_aligned::<B, ALIGN>(&data.as_ref(), offset)? as usize;
+        decode_solidity_map_tuple::<K, V, B, ALIGN, SOL_MODE>(&data, start_offset).map(Self)
+    }
 
-        // Read length
-        let length = read_u32_aligned::<B, { ALIGN }>(buf, start_offset)? as usize;
-        if length == 0 {
-            return Ok(HashSet::new());
+    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        if !SOL_MODE {
+            return <BTreeMap<K, V> as Encoder<B, ALIGN, SOL_MODE>>::partial_decode(buf, offset);
         }
 
-        // Read relative data offset (relative to the current offset)
-        let values_offset =
-            read_u32_aligned::<B, { ALIGN }>(buf, start_offset + DATA_OFFSET)? as usize;
+        // Return the offset of the tuple and the number of entries
+        let start_offset = read_u32_aligned::<B, ALIGN>(buf, offset)? as usize;
+        let keys_offset = read_u32_aligned::<B, ALIGN>(buf, start_offset)? as usize;
+        let length = read_u32_aligned::<B, ALIGN>(buf, start_offset + keys_offset)? as usize;
 
-        // Calculate absolute offset
-        let values_start = values_offset
-            .checked_add(start_offset)
-            .and_then(|sum| sum.checked_add(DATA_OFFSET))
-            .ok_or_else(|| CodecError::Decoding(DecodingError::Overflow))?;
+        Ok((start_offset, length))
+    }
+}
 
-        let mut result = HashSet::with_capacity(length);
+/// Appends the `(K[] keys, V[] values)` tuple of a `SolidityMap` to the end of the buffer.
+fn encode_solidity_map_tuple<K, V, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    map: &BTreeMap<K, V>,
+    buf: &mut impl EncodeSink,
+) -> Result<(), CodecError>
+where
+    K: Encoder<B, { ALIGN }, { SOL_MODE }>,
+    V: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let start_offset = buf.len();
+    ensure_buf_size(buf, start_offset + 64)?;
 
-        let values_data = &buf.chunk()[values_start + 32..];
+    // Both array offsets are relative to the start of the tuple
+    write_u32_aligned::<B, ALIGN>(buf, start_offset, (buf.len() - start_offset) as u32)?;
+    write_elements_solidity::<K, B, ALIGN, SOL_MODE>(buf, map.keys())?;
 
-        for i in 0..length {
-            let value_offset = align_up::<{ ALIGN }>(T::HEADER_SIZE)
-                .checked_mul(i)
-                .ok_or_else(|| CodecError::Decoding(DecodingError::Overflow))?;
+    write_u32_aligned::<B, ALIGN>(buf, start_offset + 32, (buf.len() - start_offset) as u32)?;
+    write_elements_solidity::<V, B, ALIGN, SOL_MODE>(buf, map.values())?;
 
-            let value = T::decode(&values_data, value_offset)?;
-            result.insert(value);
-        }
+    Ok(())
+}
 
-        Ok(result)
+/// Decodes the `(K[] keys, V[] values)` tuple of a `SolidityMap` starting at `start_offset`.
+fn decode_solidity_map_tuple<K, V, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    data: &[u8],
+    start_offset: usize,
+) -> Result<BTreeMap<K, V>, CodecError>
+where
+    K: Encoder<B, { ALIGN }, { SOL_MODE }> + Ord,
+    V: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let buf = &data;
+    let read_array = |array_offset: usize| -> Result<(usize, &[u8]), CodecError> {
+        let data_offset = start_offset
+            .checked_add(read_u32_aligned::<B, ALIGN>(buf, start_offset + array_offset)? as usize)
+            .ok_or(CodecError::Decoding(DecodingError::Overflow))?;
+        let length = read_u32_aligned::<B, ALIGN>(buf, data_offset)? as usize;
+        Ok((length, &data[data_offset + 32..]))
+    };
+
+    let (keys_length, keys_data) = read_array(0)?;
+    let (values_length, values_data) = read_array(32)?;
+    if keys_length != values_length {
+        return Err(CodecError::Decoding(DecodingError::InvalidData(format!(
+            "Expected {} values, but found {}",
+            keys_length, values_length
+        ))));
     }
 
-    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
-        let aligned_offset = align_up::<ALIGN>(offset);
-        let aligned_header_size = align_up::<ALIGN>(Self::HEADER_SIZE);
-
-        if buf.remaining() < aligned_offset + aligned_header_size {
-            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
-                expected: aligned_offset + aligned_header_size,
-                found: buf.remaining(),
-                msg: "Not enough data to decode HashSet header".to_string(),
-            }));
-        }
-
-        let data_offset = read_u32_aligned::<B, { ALIGN }>(buf, aligned_offset)? as usize;
-        let start_offset = aligned_offset + data_offset;
-        let length = read_u32_aligned::<B, { ALIGN }>(buf, start_offset)? as usize;
-        let values_offset = read_u32_aligned::<B, { ALIGN }>(buf, start_offset + 64)? as usize;
-        let values_start = start_offset + 64 + values_offset;
-
-        let data_length = length * align_up::<{ ALIGN }>(T::HEADER_SIZE);
+    let keys = decode_elements::<K, B, ALIGN, SOL_MODE>(&keys_data, keys_length)?;
+    let values = decode_elements::<V, B, ALIGN, SOL_MODE>(&values_data, values_length)?;
 
-        Ok((values_start + 32, data_length))
-    }
+    let result: BTreeMap<K, V> = keys.into_iter().zip(values).collect();
+    check_decoded_len(keys_length, result.len())?;
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -508,9 +1045,11 @@ mod tests {
     use super::*;
     use crate::encoder::{is_big_endian, FluentABI, SolidityABI};
     use alloc::vec::Vec;
-    use byteorder::BE;
+    use byteorder::{BE, LE};
     use bytes::BytesMut;
+    use core::hash::BuildHasherDefault;
     use hashbrown::HashMap;
+    use std::collections::{hash_map::DefaultHasher, HashMap as StdHashMap, HashSet as StdHashSet};
 
     pub fn print_bytes<B: ByteOrder, const ALIGN: usize>(buf: &[u8]) {
         for (i, chunk) in buf.chunks(ALIGN).enumerate() {
@@ -665,4 +1204,491 @@ mod tests {
             "Solidity encoding is not sorted"
         );
     }
+
+    fn encode_fluent<T: Encoder<LE, 4, false>>(value: &T) -> BytesMut {
+        let mut buf = BytesMut::new();
+        FluentABI::encode(value, &mut buf, 0).unwrap();
+        buf
+    }
+
+    fn encode_sol<T: Encoder<BE, 32, true>>(value: &T) -> BytesMut {
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(value, &mut buf, 0).unwrap();
+        buf
+    }
+
+    #[test]
+    fn test_map_types_are_byte_identical() {
+        let entries = [(3u32, 30u64), (1, 10), (7, 70), (2, 20)];
+
+        let hash_map: HashMap<u32, u64> = entries.into_iter().collect();
+        let btree_map: BTreeMap<u32, u64> = entries.into_iter().collect();
+        let std_map: std::collections::HashMap<u32, u64> = entries.into_iter().collect();
+        let custom_hasher_map: HashMap<u32, u64, BuildHasherDefault<DefaultHasher>> =
+            entries.into_iter().rev().collect();
+
+        let expected = encode_fluent(&hash_map);
+        assert_eq!(encode_fluent(&btree_map), expected);
+        assert_eq!(encode_fluent(&std_map), expected);
+        assert_eq!(encode_fluent(&custom_hasher_map), expected);
+
+        let expected = encode_sol(&hash_map);
+        assert_eq!(encode_sol(&btree_map), expected);
+        assert_eq!(encode_sol(&std_map), expected);
+        assert_eq!(encode_sol(&custom_hasher_map), expected);
+
+        let encoded = encode_fluent(&hash_map);
+        assert_eq!(
+            FluentABI::<BTreeMap<u32, u64>>::decode(&encoded, 0).unwrap(),
+            btree_map
+        );
+        assert_eq!(
+            FluentABI::<std::collections::HashMap<u32, u64>>::decode(&encoded, 0).unwrap(),
+            std_map
+        );
+
+        let encoded = encode_sol(&btree_map);
+        assert_eq!(
+            SolidityABI::<HashMap<u32, u64>>::decode(&encoded, 0).unwrap(),
+            hash_map
+        );
+        assert_eq!(
+            SolidityABI::<HashMap<u32, u64, BuildHasherDefault<DefaultHasher>>>::decode(
+                &encoded, 0
+            )
+            .unwrap(),
+            custom_hasher_map
+        );
+    }
+
+    #[test]
+    fn test_set_types_are_byte_identical() {
+        let elements = [5u32, 1, 9, 3];
+
+        let hash_set: HashSet<u32> = elements.into_iter().collect();
+        let btree_set: BTreeSet<u32> = elements.into_iter().collect();
+        let std_set: std::collections::HashSet<u32> = elements.into_iter().collect();
+        let custom_hasher_set: HashSet<u32, BuildHasherDefault<DefaultHasher>> =
+            elements.into_iter().rev().collect();
+
+        let expected = encode_fluent(&hash_set);
+        assert_eq!(encode_fluent(&btree_set), expected);
+        assert_eq!(encode_fluent(&std_set), expected);
+        assert_eq!(encode_fluent(&custom_hasher_set), expected);
+
+        let expected = encode_sol(&hash_set);
+        assert_eq!(encode_sol(&btree_set), expected);
+        assert_eq!(encode_sol(&std_set), expected);
+        assert_eq!(encode_sol(&custom_hasher_set), expected);
+
+        let encoded = encode_fluent(&std_set);
+        assert_eq!(
+            FluentABI::<BTreeSet<u32>>::decode(&encoded, 0).unwrap(),
+            btree_set
+        );
+
+        let encoded = encode_sol(&hash_set);
+        assert_eq!(
+            SolidityABI::<std::collections::HashSet<u32>>::decode(&encoded, 0).unwrap(),
+            std_set
+        );
+    }
+
+    #[test]
+    fn test_map_duplicate_keys() {
+        // There is no way to build a map with equal keys, so patch the encoded one
+        let mut encoded = encode_fluent(&BTreeMap::from([(1u32, 10u32), (2, 20)]));
+        // Overwrite the second key with the first one
+        let keys_offset = read_u32_aligned::<LE, 4>(&encoded, 4).unwrap() as usize;
+        encoded[keys_offset + 4..keys_offset + 8].copy_from_slice(&1u32.to_le_bytes());
+
+        let result = FluentABI::<BTreeMap<u32, u32>>::decode(&encoded, 0);
+        assert!(matches!(
+            result,
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn test_index_map_keeps_insertion_order() {
+        use indexmap::IndexMap;
+
+        let original: IndexMap<u32, u64> = [(3, 30), (1, 10), (2, 20)].into_iter().collect();
+
+        // Same layout as a `HashMap`, but the entries aren't sorted
+        let encoded = encode_fluent(&original);
+        assert_eq!(
+            hex::encode(&encoded),
+            "03000000\
+             140000000c000000\
+             2000000018000000\
+             030000000100000002000000\
+             1e000000000000000a000000000000001400000000000000"
+        );
+        let decoded = FluentABI::<IndexMap<u32, u64>>::decode(&encoded, 0).unwrap();
+        assert!(decoded.iter().eq(original.iter()));
+
+        let encoded = encode_sol(&original);
+        let decoded = SolidityABI::<IndexMap<u32, u64>>::decode(&encoded, 0).unwrap();
+        assert!(decoded.iter().eq(original.iter()));
+
+        // Sorted index map matches the hash map encoding
+        let mut sorted = original.clone();
+        sorted.sort_keys();
+        let hash_map: HashMap<u32, u64> = original.into_iter().collect();
+        assert_eq!(encode_fluent(&sorted), encode_fluent(&hash_map));
+        assert_eq!(encode_sol(&sorted), encode_sol(&hash_map));
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn test_index_set_keeps_insertion_order() {
+        use indexmap::IndexSet;
+
+        let original: IndexSet<u32> = [9, 4, 7].into_iter().collect();
+
+        let encoded = encode_fluent(&original);
+        assert_eq!(
+            hex::encode(&encoded),
+            "030000000c0000000c000000090000000400000007000000"
+        );
+        let decoded = FluentABI::<IndexSet<u32>>::decode(&encoded, 0).unwrap();
+        assert!(decoded.iter().eq(original.iter()));
+
+        let encoded = encode_sol(&original);
+        let decoded = SolidityABI::<IndexSet<u32>>::decode(&encoded, 0).unwrap();
+        assert!(decoded.iter().eq(original.iter()));
+
+        let mut sorted = original.clone();
+        sorted.sort();
+        let hash_set: HashSet<u32> = original.into_iter().collect();
+        assert_eq!(encode_fluent(&sorted), encode_fluent(&hash_set));
+        assert_eq!(encode_sol(&sorted), encode_sol(&hash_set));
+    }
+
+    #[test]
+    fn test_nested_map_solidity() {
+        let mut values = HashMap::new();
+        values.insert(1, HashMap::from([(5, 6)]));
+        values.insert(2, HashMap::from([(7, 8)]));
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&values, &mut buf, 0).unwrap();
+        let encoded = buf.freeze();
+        print_bytes::<BE, 32>(&encoded);
+
+        let words = encoded
+            .chunks(32)
+            .map(|word| read_u32_aligned::<BE, 32>(&word, 0).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            words,
+            [
+                32, 2, 64, 128, // outer map header
+                2, 1, 2, // keys
+                2, 64, 288, // values are offsets of the inner maps
+                1, 64, 96, 1, 5, 1, 6, // inner map 1
+                1, 64, 96, 1, 7, 1, 8, // inner map 2
+            ]
+        );
+
+        let values2 = SolidityABI::<HashMap<i32, HashMap<i32, i32>>>::decode(&encoded, 0).unwrap();
+        assert_eq!(values, values2);
+    }
+
+    fn assert_round_trip<T>(value: T)
+    where
+        T: Encoder<LE, 4, false> + Encoder<BE, 32, true> + PartialEq + core::fmt::Debug,
+    {
+        let encoded = encode_fluent(&value);
+        let decoded = FluentABI::<T>::decode(&encoded, 0).unwrap();
+        assert_eq!(decoded, value, "fluent round trip");
+
+        let encoded = encode_sol(&value);
+        let decoded = SolidityABI::<T>::decode(&encoded, 0).unwrap();
+        assert_eq!(decoded, value, "solidity round trip");
+    }
+
+    #[test]
+    fn test_map_dynamic_keys_and_values() {
+        use alloc::string::String;
+        use alloy_primitives::Bytes;
+
+        assert_round_trip(HashMap::from([
+            (String::from("a"), Bytes::from(vec![1u8, 2])),
+            (String::from("b"), Bytes::new()),
+            (
+                String::from("a key that doesn't fit into a single word"),
+                Bytes::from(vec![3u8; 40]),
+            ),
+        ]));
+        assert_round_trip(HashMap::from([
+            (1u32, vec![1u32, 2, 3]),
+            (2, vec![]),
+            (3, vec![4]),
+        ]));
+        assert_round_trip(HashMap::from([
+            (vec![1u32, 2], String::from("x")),
+            (vec![], String::new()),
+        ]));
+        assert_round_trip(HashSet::from([
+            String::from("a"),
+            String::from("bcd"),
+            String::new(),
+        ]));
+        assert_round_trip(HashSet::from([vec![1u32], vec![2, 3], vec![]]));
+    }
+
+    #[test]
+    fn test_map_multi_level_nesting() {
+        use alloc::string::String;
+
+        let inner =
+            |key: &str, values: &[u32]| HashMap::from([(String::from(key), values.to_vec())]);
+
+        // map -> map -> vector
+        assert_round_trip(HashMap::from([
+            (1u32, inner("x", &[1, 2])),
+            (2, HashMap::new()),
+            (3, inner("long key that spans more than one word", &[3])),
+        ]));
+
+        // map -> map -> map -> set
+        assert_round_trip(HashMap::from([
+            (
+                String::from("outer"),
+                HashMap::from([
+                    (
+                        1u64,
+                        BTreeMap::from([(vec![1u32], HashSet::from([String::from("q")]))]),
+                    ),
+                    (2, BTreeMap::from([(vec![], HashSet::new())])),
+                ]),
+            ),
+            (String::from("empty"), HashMap::new()),
+        ]));
+
+        // vector -> map -> set
+        assert_round_trip(vec![
+            HashMap::from([(1u32, HashSet::from([String::from("a")]))]),
+            HashMap::new(),
+            HashMap::from([(2, HashSet::from([String::from("b"), String::from("c")]))]),
+        ]);
+    }
+
+    #[test]
+    fn test_map_inside_containers() {
+        use alloc::string::String;
+
+        assert_round_trip((7u32, HashMap::from([(1u32, 2u32), (3, 4)]), 9u32));
+        assert_round_trip((
+            HashMap::from([(1u32, String::from("one"))]),
+            String::from("hi"),
+        ));
+        assert_round_trip((String::from("hi"), HashSet::from([1u32, 2]), vec![5u8]));
+        assert_round_trip(Some(HashMap::from([(1u32, String::from("x"))])));
+    }
+
+    #[test]
+    fn test_solidity_map_matches_abi_encode() {
+        use alloc::string::String;
+        use alloy_sol_types::SolValue;
+
+        let map: SolidityMap<u32, String> = [
+            (3, String::from("three")),
+            (1, String::from("one")),
+            (
+                2,
+                String::from("a value that doesn't fit into a single word"),
+            ),
+        ]
+        .into_iter()
+        .collect();
+
+        let encoded = encode_sol(&map);
+
+        let keys: Vec<u32> = map.keys().copied().collect();
+        let values: Vec<String> = map.values().cloned().collect();
+        assert_eq!(
+            encoded.to_vec(),
+            (keys.clone(), values.clone()).abi_encode()
+        );
+        // The params form is `abi.encode(keys, values)` as a whole
+        let mut params = BytesMut::new();
+        map.encode_params(&mut params).unwrap();
+        assert_eq!(params.to_vec(), (keys, values).abi_encode_params());
+        assert_eq!(SolidityMap::decode_params(&params, 0).unwrap(), map);
+
+        let decoded = SolidityABI::<SolidityMap<u32, String>>::decode(&encoded, 0).unwrap();
+        assert_eq!(decoded, map);
+
+        // Fluent mode uses the regular map layout
+        let btree_map = map.clone().into_inner();
+        assert_eq!(encode_fluent(&map), encode_fluent(&btree_map));
+        let decoded = FluentABI::<SolidityMap<u32, String>>::decode(&encode_fluent(&map), 0);
+        assert_eq!(decoded.unwrap(), map);
+    }
+
+    #[test]
+    fn test_solidity_map_nested() {
+        use alloy_primitives::{Address, U256};
+        use alloy_sol_types::SolValue;
+
+        let map = SolidityMap::from(BTreeMap::from([
+            (Address::repeat_byte(2), vec![U256::from(1), U256::from(2)]),
+            (Address::repeat_byte(1), vec![]),
+        ]));
+        let original = (7u64, map.clone(), true);
+
+        let encoded = encode_sol(&original);
+
+        let keys: Vec<Address> = map.keys().copied().collect();
+        let values: Vec<Vec<U256>> = map.values().cloned().collect();
+        assert_eq!(encoded.to_vec(), (7u64, (keys, values), true).abi_encode());
+
+        let decoded =
+            SolidityABI::<(u64, SolidityMap<Address, Vec<U256>>, bool)>::decode(&encoded, 0)
+                .unwrap();
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn test_solidity_map_composite_values() {
+        use crate::Codec;
+        use alloy_primitives::Bytes;
+        use alloy_sol_types::SolValue;
+
+        #[derive(Codec, Clone, Debug, PartialEq)]
+        struct Entry {
+            id: u32,
+            data: Bytes,
+        }
+
+        let entries = [
+            (1u32, 10u32, Bytes::from_static(b"one")),
+            (2, 20, Bytes::new()),
+            (3, 30, Bytes::from(vec![0xAB; 40])),
+        ];
+
+        let keys: Vec<u32> = entries.iter().map(|(key, ..)| *key).collect();
+        let values: Vec<(u32, Bytes)> = entries
+            .iter()
+            .map(|(_, id, data)| (*id, data.clone()))
+            .collect();
+        let expected = (keys, values).abi_encode();
+
+        let map: SolidityMap<u32, (u32, Bytes)> = entries
+            .iter()
+            .map(|(key, id, data)| (*key, (*id, data.clone())))
+            .collect();
+        let encoded = encode_sol(&map);
+        assert_eq!(encoded.to_vec(), expected);
+        assert_eq!(SolidityABI::encoded_len(&map), encoded.len());
+        assert_eq!(
+            SolidityABI::<SolidityMap<u32, (u32, Bytes)>>::decode(&encoded, 0).unwrap(),
+            map
+        );
+
+        let map: SolidityMap<u32, Entry> = entries
+            .iter()
+            .map(|(key, id, data)| {
+                let entry = Entry {
+                    id: *id,
+                    data: data.clone(),
+                };
+                (*key, entry)
+            })
+            .collect();
+        let encoded = encode_sol(&map);
+        assert_eq!(encoded.to_vec(), expected);
+        assert_eq!(SolidityABI::encoded_len(&map), encoded.len());
+        assert_eq!(
+            SolidityABI::<SolidityMap<u32, Entry>>::decode(&encoded, 0).unwrap(),
+            map
+        );
+    }
+
+    #[test]
+    fn test_solidity_map_length_mismatch() {
+        use alloy_sol_types::SolValue;
+
+        let encoded = (vec![1u32, 2], vec![10u32]).abi_encode();
+        let result = SolidityABI::<SolidityMap<u32, u32>>::decode(&&encoded[..], 0);
+        assert!(matches!(
+            result,
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+    }
+
+    #[test]
+    fn test_byte_ordered_map_order() {
+        let map = ByteOrdered(HashMap::from([(1i32, 10u32), (256, 20), (-1, 30)]));
+
+        // Little-endian keys are ordered by their lowest byte first
+        let encoded = encode_fluent(&map);
+        let entries = decode_map_entries::<i32, u32, LE, 4, false>(&encoded, 0).unwrap();
+        assert_eq!(entries, [(256, 20), (1, 10), (-1, 30)]);
+
+        // Big-endian words keep the numeric order, negative numbers are sign-extended
+        let encoded = encode_sol(&map);
+        let entries = decode_map_entries::<i32, u32, BE, 32, true>(&encoded, 0).unwrap();
+        assert_eq!(entries, [(1, 10), (256, 20), (-1, 30)]);
+
+        let decoded = SolidityABI::<ByteOrdered<HashMap<i32, u32>>>::decode(&encoded, 0).unwrap();
+        assert_eq!(decoded, map);
+
+        let std_map = ByteOrdered(
+            map.iter()
+                .map(|(k, v)| (*k, *v))
+                .collect::<StdHashMap<_, _>>(),
+        );
+        assert_eq!(encode_fluent(&std_map), encode_fluent(&map));
+        assert_eq!(encode_sol(&std_map), encode_sol(&map));
+    }
+
+    /// A key without `Ord`
+    #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+    struct Key(u32);
+
+    impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, ALIGN, SOL_MODE> for Key
+    where
+        u32: Encoder<B, ALIGN, SOL_MODE>,
+    {
+        const HEADER_SIZE: usize = <u32 as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+        const IS_DYNAMIC: bool = false;
+
+        fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+            <u32 as Encoder<B, ALIGN, SOL_MODE>>::encode(&self.0, buf, offset)
+        }
+
+        fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+            <u32 as Encoder<B, ALIGN, SOL_MODE>>::decode(buf, offset).map(Key)
+        }
+
+        fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+            <u32 as Encoder<B, ALIGN, SOL_MODE>>::partial_decode(buf, offset)
+        }
+    }
+
+    #[test]
+    fn test_byte_ordered_keys_without_ord() {
+        let map = ByteOrdered(HashMap::from([(Key(3), Key(30)), (Key(1), Key(10))]));
+        let set = ByteOrdered(StdHashSet::from([Key(5), Key(2), Key(9)]));
+
+        // Same bytes as the `Ord` order for unsigned keys in Solidity mode
+        let expected = HashMap::from([(3u32, 30u32), (1, 10)]);
+        assert_eq!(encode_sol(&map), encode_sol(&expected));
+        assert_eq!(encode_sol(&set), encode_sol(&HashSet::from([5u32, 2, 9])));
+
+        let decoded = FluentABI::<ByteOrdered<HashMap<Key, Key>>>::decode(&encode_fluent(&map), 0);
+        assert_eq!(decoded.unwrap(), map);
+        let decoded = SolidityABI::<ByteOrdered<HashMap<Key, Key>>>::decode(&encode_sol(&map), 0);
+        assert_eq!(decoded.unwrap(), map);
+
+        let decoded = FluentABI::<ByteOrdered<StdHashSet<Key>>>::decode(&encode_fluent(&set), 0);
+        assert_eq!(decoded.unwrap(), set);
+        let decoded = SolidityABI::<ByteOrdered<StdHashSet<Key>>>::decode(&encode_sol(&set), 0);
+        assert_eq!(decoded.unwrap(), set);
+    }
 }
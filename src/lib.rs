@@ -2,18 +2,49 @@
 #![allow(unused_imports)]
 extern crate alloc;
 
+mod bigint;
+mod borrowed;
 pub mod bytes;
 mod empty;
 mod encoder;
 mod error;
 mod evm;
+mod fixed;
 mod hash;
+mod packed;
 mod primitive;
+mod sink;
+mod string;
 mod tuple;
 mod vec;
+mod view;
 
+pub use borrowed::{DecodeBorrowed, DecodeShared};
+pub use bytes::ByteVec;
 pub use encoder::*;
 pub use error::*;
+pub use fixed::{Fixed, Fixed128x18, UFixed, UFixed128x18};
+pub use hash::{ByteOrdered, SolidityMap};
+pub use packed::*;
+pub use sink::{encode_to_buf_mut, CountingSink, EncodeSink, SliceSink, SubSink};
+pub use view::{
+    decode_element,
+    ByteOrderedMapView,
+    ByteOrderedSetView,
+    FluentByteOrderedMapView,
+    FluentByteOrderedSetView,
+    FluentMapView,
+    FluentSetView,
+    FluentVecView,
+    MapView,
+    SetView,
+    SolidityByteOrderedMapView,
+    SolidityByteOrderedSetView,
+    SolidityMapView,
+    SoliditySetView,
+    SolidityVecView,
+    VecView,
+};
 
 // #[cfg(test)]
 // mod tests;
@@ -0,0 +1,241 @@
+use crate::{
+    alloc::string::ToString,
+    error::{CodecError, EncodingError},
+    sink::EncodeSink,
+};
+use core::marker::PhantomData;
+
+/// Size of a Solidity word. Array elements are always padded to this size in packed mode.
+pub const PACKED_WORD_SIZE: usize = 32;
+
+/// Trait for encoding values the way Solidity's `abi.encodePacked` does.
+///
+/// The packed encoding is not self-describing:
+/// - static types use their minimal width (`uint16` takes 2 bytes, `address` takes 20 bytes)
+/// - `bytes` and `string` are written as is, without length and padding
+/// - array elements are padded to 32 bytes, exactly as in the standard ABI
+/// - tuples are the concatenation of their packed elements
+///
+/// Since there is no way to decode such data in general (Solidity doesn't provide
+/// `abi.decodePacked` either), the trait only supports encoding.
+pub trait PackedEncoder {
+    /// Size of the packed encoding for static types, `None` for dynamic ones.
+    const PACKED_SIZE: Option<usize>;
+
+    /// Size of the value encoded as an array element by [`encode_packed_array_element`], `None`
+    /// for dynamic values and types that aren't supported as array elements.
+    ///
+    /// [`encode_packed_array_element`]: PackedEncoder::encode_packed_array_element
+    const PACKED_ELEMENT_SIZE: Option<usize> = None;
+
+    /// Appends the packed encoding of the value to the end of the buffer.
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError>;
+
+    /// Appends the value encoded as an element of a packed array.
+    ///
+    /// Array elements are padded to 32 bytes. Nested arrays and tuples are not supported by
+    /// `abi.encodePacked`, so by default this method returns an error.
+    fn encode_packed_array_element(&self, _buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        Err(CodecError::Encoding(EncodingError::InvalidInputData(
+            "type is not supported as an array element in packed mode".to_string(),
+        )))
+    }
+}
+
+/// Solidity `abi.encodePacked` compatible mode.
+pub struct SolidityPackedABI<T>(PhantomData<T>);
+
+impl<T> SolidityPackedABI<T>
+where
+    T: PackedEncoder,
+{
+    pub fn is_dynamic() -> bool {
+        T::PACKED_SIZE.is_none()
+    }
+
+    /// Appends the packed encoding of the value to the end of the buffer, any [`EncodeSink`]
+    /// like `BytesMut`, `Vec<u8>` or a fixed slice.
+    pub fn encode(value: &T, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        value.encode_packed(buf)
+    }
+}
+
+/// Appends `data` to the buffer, left-padded to a 32-byte word with `fill` bytes.
+/// Used for numbers and addresses, `fill` is `0xFF` for negative signed integers.
+pub fn write_packed_word(
+    buf: &mut impl EncodeSink,
+    data: &[u8],
+    fill: u8,
+) -> Result<(), CodecError> {
+    debug_assert!(data.len() <= PACKED_WORD_SIZE);
+    let start = buf.len();
+    let padding = PACKED_WORD_SIZE - data.len();
+    buf.resize_zeroed(start + PACKED_WORD_SIZE)?;
+    if fill != 0 {
+        buf.fill_at(start, padding, fill)?;
+    }
+    buf.write_at(start + padding, data)
+}
+
+/// Appends `data` to the buffer, right-padded with zeroes to a multiple of 32 bytes.
+/// Used for `bytesN`, `bytes` and `string` values.
+pub fn write_packed_right_padded(buf: &mut impl EncodeSink, data: &[u8]) -> Result<(), CodecError> {
+    let start = buf.len();
+    buf.resize_zeroed(start + data.len().next_multiple_of(PACKED_WORD_SIZE))?;
+    buf.write_at(start, data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sink::{CountingSink, SliceSink};
+    use alloc::vec::Vec;
+    use alloy_primitives::{Address, Bytes, FixedBytes, U256};
+    use alloy_sol_types::SolValue;
+    use bytes::BytesMut;
+
+    #[test]
+    fn test_packed_primitives() {
+        let value = (true, 0x12u8, 0x3456u16, -2i32, 7u64);
+
+        let mut buf = BytesMut::new();
+        SolidityPackedABI::encode(&value, &mut buf).unwrap();
+
+        assert_eq!(hex::encode(&buf), "01123456fffffffe0000000000000007");
+
+        let value = (true, 0x3456u16, -2i32, 7u64);
+
+        let mut buf = BytesMut::new();
+        SolidityPackedABI::encode(&value, &mut buf).unwrap();
+
+        assert_eq!(buf.to_vec(), value.abi_encode_packed());
+    }
+
+    #[test]
+    fn test_packed_evm_types() {
+        let value = (
+            Address::repeat_byte(0xAA),
+            FixedBytes::<4>::from([1, 2, 3, 4]),
+            U256::from(0x0102),
+            Bytes::from_static(b"hello"),
+        );
+
+        let mut buf = BytesMut::new();
+        SolidityPackedABI::encode(&value, &mut buf).unwrap();
+
+        assert_eq!(buf.len(), 20 + 4 + 32 + 5);
+        assert_eq!(buf.to_vec(), value.abi_encode_packed());
+    }
+
+    #[test]
+    fn test_packed_arrays_are_padded() {
+        let value: (Vec<u16>, [Address; 2]) = (
+            vec![1, 2],
+            [Address::repeat_byte(0x11), Address::repeat_byte(0x22)],
+        );
+
+        let mut buf = BytesMut::new();
+        SolidityPackedABI::encode(&value, &mut buf).unwrap();
+
+        assert_eq!(buf.len(), 4 * 32);
+        assert_eq!(buf.to_vec(), value.abi_encode_packed());
+    }
+
+    #[test]
+    fn test_packed_array_elements_follow_solidity_padding() {
+        // Signed elements are sign-extended and fixed bytes are right-padded, like in the
+        // standard ABI encoding.
+        let value: (Vec<i16>, Vec<FixedBytes<2>>) =
+            (vec![-1], vec![FixedBytes::from([0xAB, 0xCD])]);
+
+        let mut buf = BytesMut::new();
+        SolidityPackedABI::encode(&value, &mut buf).unwrap();
+
+        assert_eq!(
+            hex::encode(&buf),
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff\
+             abcd000000000000000000000000000000000000000000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn test_packed_nested_arrays_are_rejected() {
+        let value: Vec<Vec<u32>> = vec![vec![1]];
+
+        let mut buf = BytesMut::new();
+        let result = SolidityPackedABI::encode(&value, &mut buf);
+
+        assert!(matches!(
+            result,
+            Err(CodecError::Encoding(EncodingError::InvalidInputData(_)))
+        ));
+    }
+
+    #[test]
+    fn test_packed_array_size() {
+        fn encoded_len<T: PackedEncoder>(value: &T) -> Option<usize> {
+            let mut buf = BytesMut::new();
+            SolidityPackedABI::encode(value, &mut buf).ok()?;
+            Some(buf.len())
+        }
+
+        let value = [U256::from(1); 3];
+        assert_eq!(<[U256; 3]>::PACKED_SIZE, Some(96));
+        assert_eq!(encoded_len(&value), Some(96));
+
+        let value = [0x12u16, 0x34];
+        assert_eq!(<[u16; 2]>::PACKED_SIZE, encoded_len(&value));
+
+        // `bytes40` elements take two words
+        let value = [FixedBytes::<40>::repeat_byte(0xAB); 2];
+        assert_eq!(<[FixedBytes<40>; 2]>::PACKED_SIZE, Some(128));
+        assert_eq!(encoded_len(&value), Some(128));
+
+        // Nested arrays can't be encoded, so they have no size either
+        let value = [[U256::from(1); 2]; 3];
+        assert_eq!(<[[U256; 2]; 3]>::PACKED_SIZE, None);
+        assert_eq!(encoded_len(&value), None);
+    }
+
+    #[test]
+    fn test_packed_sinks() {
+        let value = (
+            -2i32,
+            Address::repeat_byte(0xAA),
+            String::from("packed"),
+            vec![1u16, 2],
+        );
+        let expected = value.abi_encode_packed();
+
+        let mut buf = Vec::new();
+        SolidityPackedABI::encode(&value, &mut buf).unwrap();
+        assert_eq!(buf, expected);
+
+        let mut memory = [0xFFu8; 128];
+        let mut sink = SliceSink::new(&mut memory);
+        SolidityPackedABI::encode(&value, &mut sink).unwrap();
+        assert_eq!(sink.written(), &expected[..]);
+
+        let mut sink = CountingSink::new();
+        SolidityPackedABI::encode(&value, &mut sink).unwrap();
+        assert_eq!(sink.len(), expected.len());
+
+        let mut memory = [0u8; 16];
+        let result = SolidityPackedABI::encode(&value, &mut SliceSink::new(&mut memory));
+        assert!(matches!(
+            result,
+            Err(CodecError::Encoding(EncodingError::BufferTooSmall { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_packed_option() {
+        let mut buf = BytesMut::new();
+        SolidityPackedABI::encode(&Some(0x1234u16), &mut buf).unwrap();
+        assert_eq!(hex::encode(&buf), "011234");
+
+        let mut buf = BytesMut::new();
+        SolidityPackedABI::encode(&None::<u16>, &mut buf).unwrap();
+        assert_eq!(hex::encode(&buf), "000000");
+    }
+}
@@ -1,27 +1,37 @@
 use crate::{
-    alloc::string::ToString,
-    encoder::{align_up, get_aligned_indices, get_aligned_slice, is_big_endian, Encoder},
+    alloc::{format, string::ToString, vec, vec::Vec},
+    encoder::{
+        align_up,
+        append_elements,
+        check_word_padding,
+        contiguous,
+        decode_each,
+        encode_each,
+        encode_zeroed,
+        encoded_tail_len,
+        ensure_buf_size,
+        get_aligned_indices,
+        is_big_endian,
+        read_u32_aligned,
+        write_aligned_word,
+        write_u32_aligned,
+        zero_tail_len,
+        zeroed_len,
+        Encoder,
+    },
     error::{CodecError, DecodingError},
+    packed::{write_packed_word, PackedEncoder, PACKED_WORD_SIZE},
+    sink::{EncodeSink, SubSink},
 };
 use byteorder::ByteOrder;
-use bytes::{Buf, BytesMut};
+use bytes::Buf;
 
 impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, ALIGN, SOL_MODE> for u8 {
     const HEADER_SIZE: usize = size_of::<u8>();
     const IS_DYNAMIC: bool = false;
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
-        let word_size = align_up::<ALIGN>(<Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
-
-        if buf.len() < offset + word_size {
-            buf.resize(offset + word_size, 0);
-        }
-
-        let write_to = get_aligned_slice::<B, ALIGN>(buf, offset, 1);
-
-        write_to[0] = *self;
-
-        Ok(())
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        write_aligned_word::<B, ALIGN>(buf, offset, &[*self], 0)
     }
 
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
@@ -35,14 +45,18 @@ impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, ALIGN, S
             }));
         }
 
-        let chunk = &buf.chunk()[offset..];
-        let value = if is_big_endian::<B>() {
-            chunk[word_size - 1]
+        let word = &contiguous(buf)?[offset..offset + word_size];
+        let value_index = if is_big_endian::<B>() {
+            word_size - 1
         } else {
-            chunk[0]
+            0
         };
 
-        Ok(value)
+        if SOL_MODE {
+            check_word_padding(word, value_index, value_index + 1, 0x00)?;
+        }
+
+        Ok(word[value_index])
     }
 
     fn partial_decode(_buf: &impl Buf, _offset: usize) -> Result<(usize, usize), CodecError> {
@@ -51,13 +65,37 @@ impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, ALIGN, S
             align_up::<ALIGN>(<Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE),
         ))
     }
+
+    fn encode_slice(
+        values: &[Self],
+        buf: &mut impl EncodeSink,
+        offset: usize,
+        elem_size: usize,
+    ) -> Result<(), CodecError> {
+        if !is_byte_per_word::<ALIGN>(elem_size) {
+            return encode_each::<Self, B, ALIGN, SOL_MODE>(values, buf, offset, elem_size);
+        }
+        encode_byte_words::<Self, B, ALIGN>(values, buf, offset, |value| (value, 0x00))
+    }
+
+    fn decode_slice(
+        buf: &impl Buf,
+        offset: usize,
+        len: usize,
+        elem_size: usize,
+    ) -> Result<Vec<Self>, CodecError> {
+        if !is_byte_per_word::<ALIGN>(elem_size) {
+            return decode_each::<Self, B, ALIGN, SOL_MODE>(buf, offset, len, elem_size);
+        }
+        decode_byte_words::<Self, B, ALIGN, SOL_MODE>(buf, offset, len, |byte| (byte, 0x00))
+    }
 }
 
 impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, ALIGN, SOL_MODE> for bool {
     const HEADER_SIZE: usize = core::mem::size_of::<bool>();
     const IS_DYNAMIC: bool = false;
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
         let value: u8 = if *self { 1 } else { 0 };
 
         <u8 as Encoder<B, { ALIGN }, { SOL_MODE }>>::encode(&value, buf, offset)
@@ -66,6 +104,13 @@ impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, ALIGN, S
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
         let value = <u8 as Encoder<B, { ALIGN }, { SOL_MODE }>>::decode(buf, offset)?;
 
+        // Solidity decoding is strict, only 0 and 1 are valid booleans
+        if SOL_MODE && value > 1 {
+            return Err(CodecError::Decoding(DecodingError::InvalidData(
+                "invalid boolean value".to_string(),
+            )));
+        }
+
         Ok(value != 0)
     }
 
@@ -74,68 +119,242 @@ impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, ALIGN, S
     }
 }
 
-macro_rules! impl_int {
-    ($typ:ty, $read_method:ident, $write_method:ident) => {
-        impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, ALIGN, SOL_MODE>
-            for $typ
-        {
-            const HEADER_SIZE: usize = core::mem::size_of::<$typ>();
-            const IS_DYNAMIC: bool = false;
+impl PackedEncoder for u8 {
+    const PACKED_SIZE: Option<usize> = Some(1);
+    const PACKED_ELEMENT_SIZE: Option<usize> = Some(PACKED_WORD_SIZE);
 
-            fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
-                let word_size =
-                    align_up::<ALIGN>(<Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        buf.append(&[*self])
+    }
 
-                if buf.len() < offset + word_size {
-                    buf.resize(offset + word_size, 0);
-                }
+    fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        write_packed_word(buf, &[*self], 0x00)
+    }
+}
 
-                let (start, end) = get_aligned_indices::<B, ALIGN>(
-                    offset,
-                    <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE,
-                );
+impl PackedEncoder for bool {
+    const PACKED_SIZE: Option<usize> = Some(1);
+    const PACKED_ELEMENT_SIZE: Option<usize> = Some(PACKED_WORD_SIZE);
 
-                B::$write_method(&mut buf[start..end], *self);
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        (*self as u8).encode_packed(buf)
+    }
 
-                // Fill the rest of the buffer with 0x00 or 0xFF depending on the sign of the
-                // integer
-                let fill_val = if *self > 0 { 0x00 } else { 0xFF };
+    fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        (*self as u8).encode_packed_array_element(buf)
+    }
+}
 
-                for i in offset..start {
-                    buf[i] = fill_val;
-                }
+/// Returns the byte used to extend the integer to the full word: `0xFF` for negative signed
+/// integers (two's complement sign extension) and `0x00` otherwise.
+macro_rules! int_fill_byte {
+    (signed, $value:expr) => {
+        if $value < 0 {
+            0xFF
+        } else {
+            0x00
+        }
+    };
+    (unsigned, $value:expr) => {
+        0x00
+    };
+}
 
-                B::$write_method(&mut buf[start..end], *self);
+/// Size of the stack block slices of integers are converted in.
+const DENSE_BLOCK_SIZE: usize = 512;
 
-                for i in end..(offset + word_size) {
-                    buf[i] = fill_val;
-                }
+/// Returns `true` if integers of `size` bytes placed `elem_size` bytes apart fill their words
+/// exactly, so a slice of them is encoded as its memory in the wire byte order.
+///
+/// Byte-sized integers (`[u8; N]`, `Vec<i8>`) only fill their words with `ALIGN` 1, otherwise
+/// every byte takes a padded word of its own, see [`is_byte_per_word`].
+const fn is_dense<const ALIGN: usize>(size: usize, elem_size: usize) -> bool {
+    elem_size == size && align_up::<ALIGN>(size) == size
+}
 
-                Ok(())
+/// Returns `true` if bytes placed `elem_size` bytes apart take a word each, so a slice of them is
+/// widened into (and narrowed from) words a block at a time instead of one by one.
+const fn is_byte_per_word<const ALIGN: usize>(elem_size: usize) -> bool {
+    elem_size == align_up::<ALIGN>(1)
+}
+
+/// Position of the byte in its word: the last byte in big-endian, the first one in little-endian.
+fn byte_index_in_word<B: ByteOrder, const ALIGN: usize>() -> usize {
+    if is_big_endian::<B>() {
+        align_up::<ALIGN>(1) - 1
+    } else {
+        0
+    }
+}
+
+/// Widens byte-sized integers into their words a block at a time, `to_byte` returns the byte of
+/// a value and the fill byte of its word.
+fn encode_byte_words<T: Copy, B: ByteOrder, const ALIGN: usize>(
+    values: &[T],
+    buf: &mut impl EncodeSink,
+    offset: usize,
+    to_byte: fn(T) -> (u8, u8),
+) -> Result<(), CodecError> {
+    let word_size = align_up::<ALIGN>(1);
+    let index = byte_index_in_word::<B, ALIGN>();
+    let start = align_up::<ALIGN>(offset);
+    ensure_buf_size(buf, start + word_size * values.len())?;
+
+    let mut block = [0u8; DENSE_BLOCK_SIZE];
+    let block_len = DENSE_BLOCK_SIZE / word_size;
+    for (i, chunk) in values.chunks(block_len).enumerate() {
+        let bytes = &mut block[..word_size * chunk.len()];
+        for (word, value) in bytes.chunks_exact_mut(word_size).zip(chunk) {
+            let (byte, fill) = to_byte(*value);
+            word.fill(fill);
+            word[index] = byte;
+        }
+        buf.write_at(start + word_size * block_len * i, bytes)?;
+    }
+    Ok(())
+}
+
+/// Narrows `len` words back into byte-sized integers, the counterpart of [`encode_byte_words`].
+/// `from_byte` returns the value of a byte and the fill byte its word must have in Solidity mode.
+fn decode_byte_words<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &impl Buf,
+    offset: usize,
+    len: usize,
+    from_byte: fn(u8) -> (T, u8),
+) -> Result<Vec<T>, CodecError> {
+    let word_size = align_up::<ALIGN>(1);
+    let index = byte_index_in_word::<B, ALIGN>();
+    let end = word_size
+        .checked_mul(len)
+        .and_then(|size| size.checked_add(offset))
+        .ok_or(CodecError::Decoding(DecodingError::Overflow))?;
+
+    if buf.remaining() < end {
+        return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+            expected: end,
+            found: buf.remaining(),
+            msg: "buf too small to decode bytes".to_string(),
+        }));
+    }
+
+    contiguous(buf)?[offset..end]
+        .chunks_exact(word_size)
+        .map(|word| {
+            let (value, fill) = from_byte(word[index]);
+            if SOL_MODE {
+                check_word_padding(word, index, index + 1, fill)?;
+            }
+            Ok(value)
+        })
+        .collect()
+}
+
+/// Encodes dense integers a block at a time, `write_into` converts a block to the wire byte order
+/// (a plain copy if it's the native one).
+fn encode_dense<T, const ALIGN: usize>(
+    values: &[T],
+    buf: &mut impl EncodeSink,
+    offset: usize,
+    write_into: fn(&[T], &mut [u8]),
+) -> Result<(), CodecError> {
+    let size = size_of::<T>();
+    let start = align_up::<ALIGN>(offset);
+    ensure_buf_size(buf, start + size_of_val(values))?;
+
+    let mut block = [0u8; DENSE_BLOCK_SIZE];
+    let block_len = DENSE_BLOCK_SIZE / size;
+    for (i, chunk) in values.chunks(block_len).enumerate() {
+        let bytes = &mut block[..size_of_val(chunk)];
+        write_into(chunk, bytes);
+        buf.write_at(start + size * block_len * i, bytes)?;
+    }
+    Ok(())
+}
+
+/// Decodes `len` dense integers at once, the counterpart of [`encode_dense`].
+fn decode_dense<T: Copy + Default>(
+    buf: &impl Buf,
+    offset: usize,
+    len: usize,
+    read_into: fn(&[u8], &mut [T]),
+) -> Result<Vec<T>, CodecError> {
+    let end = size_of::<T>()
+        .checked_mul(len)
+        .and_then(|size| size.checked_add(offset))
+        .ok_or(CodecError::Decoding(DecodingError::Overflow))?;
+
+    if buf.remaining() < end {
+        return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+            expected: end,
+            found: buf.remaining(),
+            msg: "buf too small to decode integers".to_string(),
+        }));
+    }
+
+    let mut values = vec![T::default(); len];
+    read_into(&contiguous(buf)?[offset..end], &mut values);
+    Ok(values)
+}
+
+/// Integers are written to the aligned word and extended to the full word size with their sign,
+/// exactly like Solidity does for `intN`/`uintN` types.
+///
+/// In Solidity mode decoding is strict: the word is rejected if its padding is not a valid sign
+/// extension of the value.
+///
+/// Slices of integers that fill their words exactly, like `Vec<u32>` or `[u64; N]` in Fluent mode,
+/// are converted at once with `$write_into`/`$read_into` instead of one by one. Slices of `i8`
+/// (`byte`) are widened into their words a block at a time.
+macro_rules! impl_int {
+    ($typ:ty, $sign:ident $(, $slice:ident)*) => {
+        impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, ALIGN, SOL_MODE>
+            for $typ
+        {
+            const HEADER_SIZE: usize = core::mem::size_of::<$typ>();
+            const IS_DYNAMIC: bool = false;
+
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                let value_bytes = if is_big_endian::<B>() {
+                    self.to_be_bytes()
+                } else {
+                    self.to_le_bytes()
+                };
+
+                // Fill the rest of the word with the sign extension of the integer
+                let fill_val = int_fill_byte!($sign, *self);
+                write_aligned_word::<B, ALIGN>(buf, offset, &value_bytes, fill_val)
             }
 
             fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
                 let word_size =
                     align_up::<ALIGN>(<Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
 
-                if buf.remaining() < offset + ALIGN {
+                if buf.remaining() < offset + word_size {
                     return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
-                        expected: offset + ALIGN,
+                        expected: offset + word_size,
                         found: buf.remaining(),
                         msg: "buf too small to decode value".to_string(),
                     }));
                 }
 
-                let chunk = &buf.chunk()[offset..];
+                let word = &contiguous(buf)?[offset..offset + word_size];
+                let (start, end) = get_aligned_indices::<B, ALIGN>(
+                    0,
+                    <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE,
+                );
+
+                let mut value_bytes = [0u8; core::mem::size_of::<$typ>()];
+                value_bytes.copy_from_slice(&word[start..end]);
                 let value = if is_big_endian::<B>() {
-                    B::$read_method(
-                        &chunk[word_size - <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE
-                            ..word_size],
-                    )
+                    <$typ>::from_be_bytes(value_bytes)
                 } else {
-                    B::$read_method(&chunk[..<Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE])
+                    <$typ>::from_le_bytes(value_bytes)
                 };
 
+                if SOL_MODE {
+                    check_word_padding(word, start, end, int_fill_byte!($sign, value))?;
+                }
+
                 Ok(value)
             }
 
@@ -145,162 +364,587 @@ macro_rules! impl_int {
             ) -> Result<(usize, usize), CodecError> {
                 Ok((offset, <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE))
             }
+
+            impl_int_slice!($typ, $sign $(, $slice)*);
+        }
+
+        impl PackedEncoder for $typ {
+            const PACKED_SIZE: Option<usize> = Some(core::mem::size_of::<$typ>());
+            const PACKED_ELEMENT_SIZE: Option<usize> = Some(PACKED_WORD_SIZE);
+
+            fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+                buf.append(&self.to_be_bytes())
+            }
+
+            fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+                write_packed_word(buf, &self.to_be_bytes(), int_fill_byte!($sign, *self))
+            }
         }
     };
 }
 
-impl_int!(u16, read_u16, write_u16);
-impl_int!(u32, read_u32, write_u32);
-impl_int!(u64, read_u64, write_u64);
-impl_int!(i16, read_i16, write_i16);
-impl_int!(i32, read_i32, write_i32);
-impl_int!(i64, read_i64, write_i64);
+/// Implements `encode_slice`/`decode_slice` of an integer inside its `Encoder` impl, see
+/// [`impl_int`].
+macro_rules! impl_int_slice {
+    ($typ:ty, $sign:ident) => {};
+    ($typ:ty, $sign:ident, byte) => {
+        fn encode_slice(
+            values: &[Self],
+            buf: &mut impl EncodeSink,
+            offset: usize,
+            elem_size: usize,
+        ) -> Result<(), CodecError> {
+            if !is_byte_per_word::<ALIGN>(elem_size) {
+                return encode_each::<Self, B, ALIGN, SOL_MODE>(values, buf, offset, elem_size);
+            }
+            encode_byte_words::<$typ, B, ALIGN>(values, buf, offset, |value| {
+                (value as u8, int_fill_byte!($sign, value))
+            })
+        }
 
-/// Encodes and decodes Option<T> where T is an Encoder.
-/// The encoded data is prefixed with a single byte that indicates whether the Option is Some or
-/// None. Single byte will be aligned to ALIGN.
+        fn decode_slice(
+            buf: &impl Buf,
+            offset: usize,
+            len: usize,
+            elem_size: usize,
+        ) -> Result<Vec<Self>, CodecError> {
+            if !is_byte_per_word::<ALIGN>(elem_size) {
+                return decode_each::<Self, B, ALIGN, SOL_MODE>(buf, offset, len, elem_size);
+            }
+            decode_byte_words::<$typ, B, ALIGN, SOL_MODE>(buf, offset, len, |byte| {
+                let value = byte as $typ;
+                (value, int_fill_byte!($sign, value))
+            })
+        }
+    };
+    ($typ:ty, $sign:ident, $write_into:ident, $read_into:ident) => {
+        fn encode_slice(
+            values: &[Self],
+            buf: &mut impl EncodeSink,
+            offset: usize,
+            elem_size: usize,
+        ) -> Result<(), CodecError> {
+            if !is_dense::<ALIGN>(size_of::<$typ>(), elem_size) {
+                return encode_each::<Self, B, ALIGN, SOL_MODE>(values, buf, offset, elem_size);
+            }
+            encode_dense::<$typ, ALIGN>(values, buf, offset, B::$write_into)
+        }
+
+        fn decode_slice(
+            buf: &impl Buf,
+            offset: usize,
+            len: usize,
+            elem_size: usize,
+        ) -> Result<Vec<Self>, CodecError> {
+            if !is_dense::<ALIGN>(size_of::<$typ>(), elem_size) {
+                return decode_each::<Self, B, ALIGN, SOL_MODE>(buf, offset, len, elem_size);
+            }
+            decode_dense::<$typ>(buf, offset, len, B::$read_into)
+        }
+    };
+}
+
+impl_int!(i8, signed, byte);
+impl_int!(u16, unsigned, write_u16_into, read_u16_into);
+impl_int!(u32, unsigned, write_u32_into, read_u32_into);
+impl_int!(u64, unsigned, write_u64_into, read_u64_into);
+impl_int!(u128, unsigned, write_u128_into, read_u128_into);
+impl_int!(i16, signed, write_i16_into, read_i16_into);
+impl_int!(i32, signed, write_i32_into, read_i32_into);
+impl_int!(i64, signed, write_i64_into, read_i64_into);
+impl_int!(i128, signed, write_i128_into, read_i128_into);
+
+/// Size of an element header inside a container. Dynamic elements take a single offset word in
+/// Solidity mode.
+pub(crate) const fn element_header_size<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+) -> usize
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    if SOL_MODE && T::IS_DYNAMIC {
+        32
+    } else {
+        align_up::<ALIGN>(T::HEADER_SIZE)
+    }
+}
+
+/// `Option<T>` is encoded exactly like a `(bool, T)` tuple, so Solidity contracts can decode it
+/// as such:
+/// - the flag is `true` for `Some`, the payload is the encoded value
+/// - the flag is `false` for `None`, the payload is the zero value of `T` written by
+///   [`Encoder::encode_zero`] (zeroed static values, empty bytes, strings and arrays)
+///
+/// When `T` is dynamic the option is dynamic too. In Solidity mode the header holds the offset of
+/// the `(bool, T)` tail, in Fluent mode the flag and the header of `T` are inline.
 impl<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, { ALIGN }, { SOL_MODE }>
     for Option<T>
 where
-    T: Sized + Encoder<B, { ALIGN }, { SOL_MODE }> + Default,
+    T: Sized + Encoder<B, { ALIGN }, { SOL_MODE }>,
 {
-    const HEADER_SIZE: usize = 1 + T::HEADER_SIZE;
-    const IS_DYNAMIC: bool = false;
+    const HEADER_SIZE: usize = if SOL_MODE && T::IS_DYNAMIC {
+        32
+    } else {
+        align_up::<ALIGN>(<bool as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE)
+            + align_up::<ALIGN>(T::HEADER_SIZE)
+    };
+    const IS_DYNAMIC: bool = T::IS_DYNAMIC;
+
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        let flag_size = align_up::<ALIGN>(<bool as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
-        let aligned_header = align_up::<ALIGN>(Self::HEADER_SIZE);
-        if buf.len() < offset + aligned_header {
-            buf.resize(offset + aligned_header, 0);
+        if SOL_MODE && T::IS_DYNAMIC {
+            if buf.len() < offset + 32 {
+                buf.resize_zeroed(offset + 32)?;
+            }
+
+            // Write the offset of the tail (current length of the buffer)
+            write_u32_aligned::<B, ALIGN>(buf, offset, buf.len() as u32)?;
+
+            // Offsets inside the tail are relative to its start
+            let tail_offset = buf.len();
+            buf.resize_zeroed(tail_offset + flag_size + 32)?;
+            let mut tail = SubSink::new(buf, tail_offset);
+            <bool as Encoder<B, ALIGN, SOL_MODE>>::encode(&self.is_some(), &mut tail, 0)?;
+            match self {
+                Some(inner_value) => inner_value.encode(&mut tail, flag_size)?,
+                None => T::encode_zero(&mut tail, flag_size)?,
+            }
+
+            return Ok(());
         }
-        // Get aligned slice for the option flag
-        let flag_slice = get_aligned_slice::<B, ALIGN>(buf, offset, 1);
-        flag_slice[0] = if self.is_some() { 1 } else { 0 };
 
-        let inner_offset = offset + ALIGN;
+        let header_size = <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+        if buf.len() < offset + header_size {
+            buf.resize_zeroed(offset + header_size)?;
+        }
 
+        <bool as Encoder<B, ALIGN, SOL_MODE>>::encode(&self.is_some(), buf, offset)?;
         match self {
-            Some(inner_value) => inner_value.encode(buf, inner_offset)?,
-            None => {
-                let default_value = T::default();
-                default_value.encode(buf, inner_offset)?;
-            }
-        };
+            Some(inner_value) => inner_value.encode(buf, offset + flag_size)?,
+            None => T::encode_zero(buf, offset + flag_size)?,
+        }
 
         Ok(())
     }
 
-    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
-        let aligned_header = align_up::<ALIGN>(Self::HEADER_SIZE);
+    fn encoded_len(&self) -> usize {
+        let flag_size = align_up::<ALIGN>(<bool as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
 
-        if buf.remaining() < offset + aligned_header {
-            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
-                expected: offset + aligned_header,
-                found: buf.remaining(),
-                msg: "buf too small".to_string(),
-            }));
+        if SOL_MODE && T::IS_DYNAMIC {
+            // The offset word, then the `(bool, T)` tail
+            let payload_len = match self {
+                Some(inner_value) => encoded_tail_len::<T, B, ALIGN, SOL_MODE>(inner_value),
+                None => zero_tail_len::<T, B, ALIGN, SOL_MODE>(),
+            };
+            return 32 + flag_size + 32 + payload_len;
         }
 
-        let chunk = &buf.chunk()[offset..];
-        let option_flag = if is_big_endian::<B>() {
-            chunk[aligned_header - 1]
+        <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE
+            + self.as_ref().map_or_else(
+                zero_tail_len::<T, B, ALIGN, SOL_MODE>,
+                encoded_tail_len::<T, B, ALIGN, SOL_MODE>,
+            )
+    }
+
+    /// The zero value of an option is `None`.
+    fn encode_zero(buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        None::<T>.encode(buf, offset)
+    }
+
+    fn zero_encoded_len() -> usize {
+        None::<T>.encoded_len()
+    }
+
+    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+        let flag_size = align_up::<ALIGN>(<bool as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
+
+        let data = contiguous(buf)?;
+        let (chunk, offset) = if SOL_MODE && T::IS_DYNAMIC {
+            let data_offset = read_u32_aligned::<B, ALIGN>(buf, offset)? as usize;
+            if buf.remaining() < data_offset {
+                return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+                    expected: data_offset,
+                    found: buf.remaining(),
+                    msg: "buf too small to read Option data offset".to_string(),
+                }));
+            }
+            (&data[data_offset..], 0)
         } else {
-            chunk[0]
+            (&data[..], offset)
         };
 
-        let chunk = &buf.chunk()[offset + ALIGN..];
-
-        if option_flag != 0 {
-            let inner_value = T::decode(&chunk, 0)?;
-            Ok(Some(inner_value))
+        if <bool as Encoder<B, ALIGN, SOL_MODE>>::decode(&chunk, offset)? {
+            Ok(Some(T::decode(&chunk, offset + flag_size)?))
         } else {
             Ok(None)
         }
     }
 
-    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
-        let aligned_header = align_up::<ALIGN>(Self::HEADER_SIZE);
+    fn partial_decode(_buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        Ok((offset, <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE))
+    }
+}
 
-        if buf.remaining() < offset + aligned_header {
-            return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
-                expected: offset + aligned_header,
-                found: buf.remaining(),
-                msg: "buf too small".to_string(),
-            }));
+/// Size of the `Result<T, E>` payload slot, large enough for the headers of both `T` and `E`.
+const fn result_payload_size<T, E, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>() -> usize
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+    E: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let ok_size = element_header_size::<T, B, ALIGN, SOL_MODE>();
+    let err_size = element_header_size::<E, B, ALIGN, SOL_MODE>();
+    if ok_size > err_size {
+        ok_size
+    } else {
+        err_size
+    }
+}
+
+/// `Result<T, E>` is encoded as a `u8` tag (`0` for `Ok`, `1` for `Err`) followed by the payload,
+/// i.e. as a `(uint8, T)` or `(uint8, E)` tuple. The payload slot is large enough for the headers
+/// of both types, the unused part is zeroed.
+///
+/// When `T` or `E` is dynamic the result is dynamic too. In Solidity mode the header holds the
+/// offset of the `(tag, payload)` tail, in Fluent mode the tag and the payload header are inline.
+/// Decoding fails on any tag other than `0` or `1`.
+impl<T, E, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    Encoder<B, { ALIGN }, { SOL_MODE }> for Result<T, E>
+where
+    T: Sized + Encoder<B, { ALIGN }, { SOL_MODE }>,
+    E: Sized + Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    const HEADER_SIZE: usize = if SOL_MODE && (T::IS_DYNAMIC || E::IS_DYNAMIC) {
+        32
+    } else {
+        align_up::<ALIGN>(<u8 as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE)
+            + result_payload_size::<T, E, B, ALIGN, SOL_MODE>()
+    };
+    const IS_DYNAMIC: bool = T::IS_DYNAMIC || E::IS_DYNAMIC;
+
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        if SOL_MODE && Self::IS_DYNAMIC {
+            if buf.len() < offset + 32 {
+                buf.resize_zeroed(offset + 32)?;
+            }
+
+            // Write the offset of the tail (current length of the buffer)
+            write_u32_aligned::<B, ALIGN>(buf, offset, buf.len() as u32)?;
+
+            // Offsets inside the tail are relative to its start
+            let tail_offset = buf.len();
+            encode_result::<T, E, B, ALIGN, SOL_MODE>(
+                self,
+                &mut SubSink::new(buf, tail_offset),
+                0,
+            )?;
+
+            return Ok(());
+        }
+
+        encode_result::<T, E, B, ALIGN, SOL_MODE>(self, buf, offset)
+    }
+
+    fn encoded_len(&self) -> usize {
+        let tag_size = align_up::<ALIGN>(<u8 as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
+        let len = tag_size
+            + result_payload_size::<T, E, B, ALIGN, SOL_MODE>()
+            + match self {
+                Ok(value) => encoded_tail_len::<T, B, ALIGN, SOL_MODE>(value),
+                Err(err) => encoded_tail_len::<E, B, ALIGN, SOL_MODE>(err),
+            };
+
+        if SOL_MODE && Self::IS_DYNAMIC {
+            // The offset word, then the `(tag, payload)` tail
+            32 + len
+        } else {
+            len
+        }
+    }
+
+    /// The zero value of a result is `Ok` holding the zero value of `T`.
+    fn encode_zero(buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        if SOL_MODE && Self::IS_DYNAMIC {
+            ensure_buf_size(buf, offset + 32)?;
+
+            // Write the offset of the tail (current length of the buffer)
+            let tail_offset = buf.len();
+            write_u32_aligned::<B, ALIGN>(buf, offset, tail_offset as u32)?;
+
+            return encode_zero_result::<T, E, B, ALIGN, SOL_MODE>(
+                &mut SubSink::new(buf, tail_offset),
+                0,
+            );
+        }
+
+        encode_zero_result::<T, E, B, ALIGN, SOL_MODE>(buf, offset)
+    }
+
+    fn zero_encoded_len() -> usize {
+        let tag_size = align_up::<ALIGN>(<u8 as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
+        let len = tag_size
+            + result_payload_size::<T, E, B, ALIGN, SOL_MODE>()
+            + zero_tail_len::<T, B, ALIGN, SOL_MODE>();
+
+        if SOL_MODE && Self::IS_DYNAMIC {
+            32 + len
+        } else {
+            len
         }
+    }
 
-        let chunk = &buf.chunk()[offset..];
-        let option_flag = if is_big_endian::<B>() {
-            chunk[ALIGN - 1]
+    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+        let tag_size = align_up::<ALIGN>(<u8 as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
+
+        let data = contiguous(buf)?;
+        let (chunk, offset) = if SOL_MODE && Self::IS_DYNAMIC {
+            let data_offset = read_u32_aligned::<B, ALIGN>(buf, offset)? as usize;
+            if buf.remaining() < data_offset {
+                return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+                    expected: data_offset,
+                    found: buf.remaining(),
+                    msg: "buf too small to read Result data offset".to_string(),
+                }));
+            }
+            (&data[data_offset..], 0)
         } else {
-            chunk[0]
+            (&data[..], offset)
         };
 
-        let chunk = &buf.chunk()[offset + ALIGN..];
+        match <u8 as Encoder<B, ALIGN, SOL_MODE>>::decode(&chunk, offset)? {
+            0 => Ok(Ok(T::decode(&chunk, offset + tag_size)?)),
+            1 => Ok(Err(E::decode(&chunk, offset + tag_size)?)),
+            tag => Err(CodecError::Decoding(DecodingError::InvalidData(format!(
+                "invalid Result tag: {}",
+                tag
+            )))),
+        }
+    }
 
-        if option_flag != 0 {
-            let (_, inner_size) = T::partial_decode(&chunk, 0)?;
-            Ok((offset, aligned_header + inner_size))
-        } else {
-            let aligned_data_size = align_up::<ALIGN>(T::HEADER_SIZE);
-            Ok((offset, aligned_header + aligned_data_size))
+    fn partial_decode(_buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        Ok((offset, <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE))
+    }
+}
+
+/// Writes the tag and the payload of the result at the given offset.
+fn encode_result<T, E, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    value: &Result<T, E>,
+    buf: &mut impl EncodeSink,
+    offset: usize,
+) -> Result<(), CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+    E: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let tag_size = align_up::<ALIGN>(<u8 as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
+    let payload_offset = offset + tag_size;
+    let end = payload_offset + result_payload_size::<T, E, B, ALIGN, SOL_MODE>();
+
+    if buf.len() < end {
+        buf.resize_zeroed(end)?;
+    }
+    buf.fill_at(payload_offset, end - payload_offset, 0)?;
+
+    match value {
+        Ok(value) => {
+            <u8 as Encoder<B, ALIGN, SOL_MODE>>::encode(&0, buf, offset)?;
+            value.encode(buf, payload_offset)
+        }
+        Err(err) => {
+            <u8 as Encoder<B, ALIGN, SOL_MODE>>::encode(&1, buf, offset)?;
+            err.encode(buf, payload_offset)
         }
     }
 }
 
+/// Writes the zero value of the result at the given offset: the zero tag of `Ok` and the zero
+/// value of `T`.
+fn encode_zero_result<T, E, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &mut impl EncodeSink,
+    offset: usize,
+) -> Result<(), CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+    E: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let tag_size = align_up::<ALIGN>(<u8 as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE);
+    let end = offset + tag_size + result_payload_size::<T, E, B, ALIGN, SOL_MODE>();
+
+    ensure_buf_size(buf, end)?;
+    buf.fill_at(offset, end - offset, 0)?;
+    T::encode_zero(buf, offset + tag_size)
+}
+
+/// Packed `Option<T>` is encoded as `abi.encodePacked(bool, T)`, with the zero value of `T`
+/// for `None` (no bytes at all if `T` is dynamic).
+impl<T: PackedEncoder> PackedEncoder for Option<T> {
+    const PACKED_SIZE: Option<usize> = match T::PACKED_SIZE {
+        Some(size) => Some(1 + size),
+        None => None,
+    };
+
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        match self {
+            Some(inner_value) => {
+                true.encode_packed(buf)?;
+                inner_value.encode_packed(buf)
+            }
+            None => {
+                false.encode_packed(buf)?;
+                buf.resize_zeroed(buf.len() + T::PACKED_SIZE.unwrap_or(0))?;
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Fixed size arrays are encoded like tuples of `N` elements of the same type:
+/// - static elements are written inline, one after another
+/// - in Solidity mode an array of dynamic elements is dynamic itself: the header holds the offset
+///   of the tail, the tail holds the element headers (offsets) followed by the elements data
+/// - in Fluent mode element headers are always inline and dynamic data is appended to the buffer
 impl<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool, const N: usize>
     Encoder<B, { ALIGN }, { SOL_MODE }> for [T; N]
 where
-    T: Sized + Encoder<B, { ALIGN }, { SOL_MODE }> + Default + Copy,
+    T: Sized + Encoder<B, { ALIGN }, { SOL_MODE }>,
 {
-    const HEADER_SIZE: usize = T::HEADER_SIZE * N;
-    const IS_DYNAMIC: bool = false;
+    const HEADER_SIZE: usize = if SOL_MODE && T::IS_DYNAMIC {
+        32
+    } else {
+        align_up::<ALIGN>(T::HEADER_SIZE) * N
+    };
+    const IS_DYNAMIC: bool = T::IS_DYNAMIC;
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
-        let item_size = align_up::<ALIGN>(T::HEADER_SIZE);
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        let item_size = element_header_size::<T, B, ALIGN, SOL_MODE>();
+
+        if SOL_MODE && T::IS_DYNAMIC {
+            if buf.len() < offset + 32 {
+                buf.resize_zeroed(offset + 32)?;
+            }
+
+            // Write the offset of the tail (current length of the buffer)
+            write_u32_aligned::<B, ALIGN>(buf, offset, buf.len() as u32)?;
+
+            // Element offsets are relative to the start of the tail
+            append_elements::<T, B, ALIGN, SOL_MODE>(buf, self.iter(), item_size)?;
+
+            return Ok(());
+        }
 
         if buf.len() < offset + (item_size * N) {
-            buf.resize(offset + (item_size * N), 0);
+            buf.resize_zeroed(offset + (item_size * N))?;
         }
 
-        for (i, item) in self.iter().enumerate() {
-            item.encode(buf, offset + (item_size * i))?;
+        T::encode_slice(self, buf, offset, item_size)
+    }
+
+    fn encoded_len(&self) -> usize {
+        let len = element_header_size::<T, B, ALIGN, SOL_MODE>() * N
+            + self
+                .iter()
+                .map(encoded_tail_len::<T, B, ALIGN, SOL_MODE>)
+                .sum::<usize>();
+
+        if SOL_MODE && T::IS_DYNAMIC {
+            // The offset word, then the elements
+            32 + len
+        } else {
+            len
+        }
+    }
+
+    /// The zero value of an array holds `N` zero values of `T`.
+    fn encode_zero(buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        if !(SOL_MODE && T::IS_DYNAMIC) {
+            return encode_zeroed::<Self, B, ALIGN, SOL_MODE>(buf, offset);
         }
 
+        ensure_buf_size(buf, offset + 32)?;
+
+        // Write the offset of the tail (current length of the buffer)
+        let tail_offset = buf.len();
+        write_u32_aligned::<B, ALIGN>(buf, offset, tail_offset as u32)?;
+
+        // Element offsets are relative to the start of the tail
+        let item_size = element_header_size::<T, B, ALIGN, SOL_MODE>();
+        buf.resize_zeroed(tail_offset + item_size * N)?;
+        let mut tail = SubSink::new(buf, tail_offset);
+        for i in 0..N {
+            T::encode_zero(&mut tail, item_size * i)?;
+        }
         Ok(())
     }
 
+    fn zero_encoded_len() -> usize {
+        if !(SOL_MODE && T::IS_DYNAMIC) {
+            return zeroed_len::<Self, B, ALIGN, SOL_MODE>();
+        }
+
+        // The offset word, then the elements
+        32 + (element_header_size::<T, B, ALIGN, SOL_MODE>()
+            + zero_tail_len::<T, B, ALIGN, SOL_MODE>())
+            * N
+    }
+
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
-        let item_size = align_up::<ALIGN>(T::HEADER_SIZE);
-        let total_size = offset + (item_size * N);
+        let item_size = element_header_size::<T, B, ALIGN, SOL_MODE>();
+
+        let data = contiguous(buf)?;
+        let (chunk, offset) = if SOL_MODE && T::IS_DYNAMIC {
+            let data_offset = read_u32_aligned::<B, ALIGN>(buf, offset)? as usize;
+            if buf.remaining() < data_offset {
+                return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+                    expected: data_offset,
+                    found: buf.remaining(),
+                    msg: "buf too small to read [T; N] data offset".to_string(),
+                }));
+            }
+            (&data[data_offset..], 0)
+        } else {
+            (&data[..], offset)
+        };
 
-        if buf.remaining() < total_size {
+        let total_size = offset + (item_size * N);
+        if chunk.len() < total_size {
             return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
                 expected: total_size,
-                found: buf.remaining(),
+                found: chunk.len(),
                 msg: "buf too small to decode [T; N]".to_string(),
             }));
         }
 
-        let mut result = [T::default(); N];
+        let items = T::decode_slice(&chunk, offset, N, item_size)?;
 
-        for (i, item) in result.iter_mut().enumerate() {
-            *item = T::decode(buf, offset + (item_size * i))?;
-        }
+        Ok(items
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("exactly N items are decoded")))
+    }
 
-        Ok(result)
+    fn partial_decode(_buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        Ok((offset, <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE))
     }
+}
 
-    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
-        let item_size = align_up::<ALIGN>(T::HEADER_SIZE);
-        let total_size = item_size * N;
+/// Elements are padded like in the standard ABI, arrays of dynamic elements and nested arrays have
+/// no fixed size (nested arrays are rejected by `encode_packed`).
+impl<T: PackedEncoder, const N: usize> PackedEncoder for [T; N] {
+    const PACKED_SIZE: Option<usize> = match T::PACKED_ELEMENT_SIZE {
+        Some(size) => Some(size * N),
+        None => None,
+    };
 
-        Ok((offset, total_size))
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        for item in self.iter() {
+            item.encode_packed_array_element(buf)?;
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::encoder::{FluentABI, SolidityABI};
     use byteorder::{BigEndian, LittleEndian};
     use bytes::{Bytes, BytesMut};
 
@@ -553,6 +1197,232 @@ mod tests {
         assert_eq!(original, decoded.unwrap());
     }
 
+    #[test]
+    fn test_option_sol_matches_bool_tuple() {
+        use alloy_sol_types::SolValue;
+
+        for original in [Some(0x12345678u32), None] {
+            let mut buf = BytesMut::new();
+            SolidityABI::encode(&original, &mut buf, 0).unwrap();
+            let expected = (original.is_some(), original.unwrap_or_default()).abi_encode();
+            assert_eq!(buf.to_vec(), expected);
+            assert_eq!(
+                SolidityABI::<Option<u32>>::decode(&buf, 0).unwrap(),
+                original
+            );
+        }
+
+        for original in [Some(Bytes::from_static(b"payload")), None] {
+            let mut buf = BytesMut::new();
+            SolidityABI::encode(&original, &mut buf, 0).unwrap();
+            let expected = (
+                original.is_some(),
+                alloy_primitives::Bytes::from(original.clone().unwrap_or_default()),
+            )
+                .abi_encode();
+            assert_eq!(buf.to_vec(), expected);
+            assert_eq!(
+                SolidityABI::<Option<Bytes>>::decode(&buf, 0).unwrap(),
+                original
+            );
+        }
+
+        let original: (Option<Vec<u32>>, u32, Option<String>) =
+            (Some(vec![1, 2, 3]), 7, Some(String::from("hello")));
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let expected = (
+            (true, vec![1u32, 2, 3]),
+            7u32,
+            (true, String::from("hello")),
+        )
+            .abi_encode();
+        assert_eq!(buf.to_vec(), expected);
+        assert_eq!(
+            SolidityABI::<(Option<Vec<u32>>, u32, Option<String>)>::decode(&buf, 0).unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn test_option_none_composite_payload_sol() {
+        use crate::Codec;
+        use alloy_sol_types::{sol_data, SolType};
+
+        #[derive(Codec, Debug, PartialEq)]
+        struct Payload {
+            id: u32,
+            data: Bytes,
+        }
+
+        type SolOption = (sol_data::Bool, (sol_data::Uint<32>, sol_data::Bytes));
+        let zero = (false, (0u32, alloy_primitives::Bytes::new()));
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&None::<(u32, Bytes)>, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), SolOption::abi_encode(&zero));
+        assert!(SolOption::abi_decode(&buf, true).is_ok());
+        assert_eq!(SolidityABI::encoded_len(&None::<(u32, Bytes)>), buf.len());
+        assert_eq!(
+            SolidityABI::<Option<(u32, Bytes)>>::decode(&buf, 0).unwrap(),
+            None
+        );
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&None::<Payload>, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), SolOption::abi_encode(&zero));
+        assert!(SolOption::abi_decode(&buf, true).is_ok());
+        assert_eq!(SolidityABI::encoded_len(&None::<Payload>), buf.len());
+        assert_eq!(
+            SolidityABI::<Option<Payload>>::decode(&buf, 0).unwrap(),
+            None
+        );
+
+        // Nested zero values: a tuple of a string and a dynamic array
+        type Nested = (String, [Bytes; 2]);
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&None::<Nested>, &mut buf, 0).unwrap();
+        let expected = <(
+            sol_data::Bool,
+            (sol_data::String, sol_data::FixedArray<sol_data::Bytes, 2>),
+        )>::abi_encode(&(
+            false,
+            (
+                String::new(),
+                [
+                    alloy_primitives::Bytes::new(),
+                    alloy_primitives::Bytes::new(),
+                ],
+            ),
+        ));
+        assert_eq!(buf.to_vec(), expected);
+        assert_eq!(SolidityABI::encoded_len(&None::<Nested>), buf.len());
+
+        // The zero value of a result is `Ok` with the zero value of `T`
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&None::<Result<Bytes, u32>>, &mut buf, 0).unwrap();
+        let expected = <(sol_data::Bool, (sol_data::Uint<8>, sol_data::Bytes))>::abi_encode(&(
+            false,
+            (0u8, alloy_primitives::Bytes::new()),
+        ));
+        assert_eq!(buf.to_vec(), expected);
+        assert_eq!(
+            SolidityABI::encoded_len(&None::<Result<Bytes, u32>>),
+            buf.len()
+        );
+    }
+
+    #[test]
+    fn test_option_dynamic_wasm() {
+        let original: (Option<Bytes>, Option<Vec<String>>, Option<String>) = (
+            Some(Bytes::from_static(b"payload")),
+            Some(vec![String::from("a"), String::from("bc")]),
+            None,
+        );
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        assert_eq!(
+            FluentABI::<(Option<Bytes>, Option<Vec<String>>, Option<String>)>::decode(&buf, 0)
+                .unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn test_option_invalid_flag_sol() {
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&Some(1u32), &mut buf, 0).unwrap();
+        buf[31] = 2;
+
+        assert!(matches!(
+            SolidityABI::<Option<u32>>::decode(&buf, 0),
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+    }
+
+    #[test]
+    fn test_result_sol() {
+        use alloy_sol_types::{sol_data, SolType};
+
+        type Static = Result<u32, u64>;
+        for (original, tag, value) in [(Static::Ok(7), 0u8, 7u64), (Static::Err(9), 1, 9)] {
+            let mut buf = BytesMut::new();
+            SolidityABI::encode(&original, &mut buf, 0).unwrap();
+            let expected = <(sol_data::Uint<8>, sol_data::Uint<64>)>::abi_encode(&(tag, value));
+            assert_eq!(buf.to_vec(), expected);
+            assert_eq!(SolidityABI::<Static>::decode(&buf, 0).unwrap(), original);
+        }
+
+        type Dynamic = Result<Bytes, String>;
+        let original = Dynamic::Ok(Bytes::from_static(b"success"));
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let expected = <(sol_data::Uint<8>, sol_data::Bytes)>::abi_encode(&(
+            0u8,
+            alloy_primitives::Bytes::from_static(b"success"),
+        ));
+        assert_eq!(buf.to_vec(), expected);
+        assert_eq!(SolidityABI::<Dynamic>::decode(&buf, 0).unwrap(), original);
+
+        let original = Dynamic::Err(String::from("failure"));
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let expected =
+            <(sol_data::Uint<8>, sol_data::String)>::abi_encode(&(1u8, String::from("failure")));
+        assert_eq!(buf.to_vec(), expected);
+        assert_eq!(SolidityABI::<Dynamic>::decode(&buf, 0).unwrap(), original);
+
+        // Static payload of a dynamic result
+        let original: Result<u32, String> = Ok(5);
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&(original.clone(), 1u32), &mut buf, 0).unwrap();
+        assert_eq!(
+            SolidityABI::<(Result<u32, String>, u32)>::decode(&buf, 0).unwrap(),
+            (original, 1)
+        );
+    }
+
+    #[test]
+    fn test_result_wasm() {
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&Result::<u32, u64>::Err(0x11), &mut buf, 0).unwrap();
+        assert_eq!(hex::encode(&buf), "010000001100000000000000");
+        assert_eq!(
+            FluentABI::<Result<u32, u64>>::decode(&buf, 0).unwrap(),
+            Err(0x11)
+        );
+
+        let original: (Result<Vec<u32>, String>, Result<u32, Bytes>, u32) =
+            (Ok(vec![1, 2, 3]), Err(Bytes::from_static(b"error")), 7);
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        assert_eq!(
+            FluentABI::<(Result<Vec<u32>, String>, Result<u32, Bytes>, u32)>::decode(&buf, 0)
+                .unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn test_result_invalid_tag() {
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&Result::<u32, u32>::Ok(1), &mut buf, 0).unwrap();
+        buf[0] = 2;
+        assert!(matches!(
+            FluentABI::<Result<u32, u32>>::decode(&buf, 0),
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&Result::<Bytes, u32>::Err(1), &mut buf, 0).unwrap();
+        buf[63] = 2;
+        assert!(matches!(
+            SolidityABI::<Result<Bytes, u32>>::decode(&buf, 0),
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+    }
+
     #[test]
     fn test_u8_array_encode_decode_le_with_alignment() {
         let original: [u8; 5] = [1, 2, 3, 4, 5];
@@ -582,4 +1452,271 @@ mod tests {
 
         assert_eq!(original, decoded);
     }
+
+    #[test]
+    fn test_int_family_matches_solidity() {
+        use alloy_sol_types::{sol_data, SolType};
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&-5i8, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), sol_data::Int::<8>::abi_encode(&-5i8));
+        assert_eq!(SolidityABI::<i8>::decode(&buf, 0).unwrap(), -5i8);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&u128::MAX, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), sol_data::Uint::<128>::abi_encode(&u128::MAX));
+        assert_eq!(SolidityABI::<u128>::decode(&buf, 0).unwrap(), u128::MAX);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&i128::MIN, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), sol_data::Int::<128>::abi_encode(&i128::MIN));
+        assert_eq!(SolidityABI::<i128>::decode(&buf, 0).unwrap(), i128::MIN);
+    }
+
+    #[test]
+    fn test_int_sign_extension() {
+        // Zero and positive values must be padded with zeroes
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&0i32, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), [0u8; 32]);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&0u64, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), [0u8; 32]);
+
+        // Negative values are sign-extended to the whole word
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&-1i16, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), [0xFFu8; 32]);
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&-2i8, &mut buf, 0).unwrap();
+        assert_eq!(hex::encode(&buf), "feffffff");
+        assert_eq!(FluentABI::<i8>::decode(&buf, 0).unwrap(), -2i8);
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&7i8, &mut buf, 0).unwrap();
+        assert_eq!(hex::encode(&buf), "07000000");
+    }
+
+    #[test]
+    fn test_int_strict_decoding() {
+        // uint8(1) with a dirty high byte
+        let mut dirty = [0u8; 32];
+        dirty[0] = 0x01;
+        dirty[31] = 0x01;
+        assert!(matches!(
+            SolidityABI::<u8>::decode(&&dirty[..], 0),
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+        assert!(SolidityABI::<u32>::decode(&&dirty[..], 0).is_err());
+
+        // int16(-1) padded with zeroes isn't a valid sign extension
+        let mut bad_sign = [0u8; 32];
+        bad_sign[30] = 0xFF;
+        bad_sign[31] = 0xFF;
+        assert!(SolidityABI::<i16>::decode(&&bad_sign[..], 0).is_err());
+        assert_eq!(
+            SolidityABI::<u16>::decode(&&bad_sign[..], 0).unwrap(),
+            u16::MAX
+        );
+
+        // Fluent mode stays lenient
+        let dirty = [0x01, 0x00, 0x00, 0xAA];
+        assert_eq!(FluentABI::<u8>::decode(&&dirty[..], 0).unwrap(), 1);
+        assert_eq!(FluentABI::<i16>::decode(&&dirty[..], 0).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_array_of_dynamic_elements_sol() {
+        use alloy_sol_types::{sol_data, SolType, SolValue};
+
+        let original = [
+            Bytes::from_static(b"a"),
+            Bytes::from_static(b"bb"),
+            Bytes::from_static(&[0xCC; 40]),
+        ];
+        let alloy_value: [alloy_primitives::Bytes; 3] = original.clone().map(Into::into);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        assert_eq!(
+            buf.to_vec(),
+            sol_data::FixedArray::<sol_data::Bytes, 3>::abi_encode(&alloy_value)
+        );
+        assert_eq!(
+            SolidityABI::<[Bytes; 3]>::decode(&buf, 0).unwrap(),
+            original
+        );
+
+        let original: (u32, [String; 2], [Vec<u32>; 2]) = (
+            7,
+            [String::from("hello"), String::from("world")],
+            [vec![1, 2, 3], vec![]],
+        );
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        assert_eq!(buf.to_vec(), original.abi_encode());
+        assert_eq!(
+            SolidityABI::<(u32, [String; 2], [Vec<u32>; 2])>::decode(&buf, 0).unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn test_nested_static_arrays_sol() {
+        use alloy_primitives::U256;
+        use alloy_sol_types::SolValue;
+
+        let original: [[U256; 2]; 3] =
+            core::array::from_fn(|i| core::array::from_fn(|j| U256::from(i * 2 + j)));
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        assert_eq!(buf.len(), 6 * 32);
+        assert_eq!(buf.to_vec(), original.abi_encode());
+        assert_eq!(
+            SolidityABI::<[[U256; 2]; 3]>::decode(&buf, 0).unwrap(),
+            original
+        );
+
+        // Small elements are still padded to a full word
+        let original = ([1u8, 2, 3], 4u32);
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        assert_eq!(buf.len(), 4 * 32);
+        assert_eq!(
+            SolidityABI::<([u8; 3], u32)>::decode(&buf, 0).unwrap(),
+            original
+        );
+    }
+
+    #[test]
+    fn test_array_of_dynamic_elements_wasm() {
+        let original: ([String; 2], u32, [[Vec<u32>; 2]; 2]) = (
+            [String::from("hello"), String::from("world")],
+            7,
+            [[vec![1], vec![2, 3]], [vec![], vec![4, 5, 6]]],
+        );
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        assert_eq!(
+            FluentABI::<([String; 2], u32, [[Vec<u32>; 2]; 2])>::decode(&buf, 0).unwrap(),
+            original
+        );
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&[[1u32, 2], [3, 4]], &mut buf, 0).unwrap();
+        assert_eq!(hex::encode(&buf), "01000000020000000300000004000000");
+    }
+
+    /// Encodes the slice through `encode_slice` and one by one and checks that the bytes are the
+    /// same, then decodes them back.
+    fn check_slice<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+        values: &[T],
+        offset: usize,
+        elem_size: usize,
+    ) where
+        T: Encoder<B, ALIGN, SOL_MODE> + PartialEq + core::fmt::Debug,
+    {
+        let mut expected = BytesMut::new();
+        encode_each::<T, B, ALIGN, SOL_MODE>(values, &mut expected, offset, elem_size).unwrap();
+        let mut buf = BytesMut::new();
+        T::encode_slice(values, &mut buf, offset, elem_size).unwrap();
+        assert_eq!(buf, expected);
+
+        let start = align_up::<ALIGN>(offset);
+        let decoded = T::decode_slice(&buf, start, values.len(), elem_size).unwrap();
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn test_dense_int_slices() {
+        // 300 values span several conversion blocks
+        let u32s: Vec<u32> = (0..300u32).map(|i| i.wrapping_mul(0x01010101)).collect();
+        let i64s: Vec<i64> = (0..300).map(|i| -i * 0x0102030405).collect();
+        let i128s: Vec<i128> = (0..40).map(|i| i128::MIN / (i + 1)).collect();
+
+        check_slice::<u32, LittleEndian, 4, false>(&u32s, 0, 4);
+        check_slice::<u32, LittleEndian, 4, false>(&u32s, 6, 4);
+        check_slice::<u32, BigEndian, 4, false>(&u32s, 0, 4);
+        check_slice::<i64, LittleEndian, 4, false>(&i64s, 0, 8);
+        check_slice::<i64, BigEndian, 4, false>(&i64s, 4, 8);
+        check_slice::<i128, LittleEndian, 4, false>(&i128s, 0, 16);
+        check_slice::<u32, LittleEndian, 4, false>(&[], 0, 4);
+
+        // Padded words are encoded one by one
+        check_slice::<i64, BigEndian, 32, true>(&i64s, 0, 32);
+        check_slice::<u16, LittleEndian, 4, false>(&[1, 2, 0xFFFF], 0, 4);
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&u32s, &mut buf, 0).unwrap();
+        assert_eq!(FluentABI::<Vec<u32>>::decode(&buf, 0).unwrap(), u32s);
+        assert_eq!(&buf[12..16], &[0, 0, 0, 0]);
+        assert_eq!(&buf[16..20], &[1, 1, 1, 1]);
+
+        let array: [u64; 3] = [1, u64::MAX, 3];
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&array, &mut buf, 0).unwrap();
+        assert_eq!(
+            hex::encode(&buf),
+            "0100000000000000ffffffffffffffff0300000000000000"
+        );
+        assert_eq!(FluentABI::<[u64; 3]>::decode(&buf, 0).unwrap(), array);
+    }
+
+    #[test]
+    fn test_byte_slices() {
+        // 600 words span several blocks in Fluent mode
+        let u8s: Vec<u8> = (0..600u32).map(|i| (i * 7) as u8).collect();
+        let i8s: Vec<i8> = (0..600u32).map(|i| (i * 7) as i8).collect();
+
+        check_slice::<u8, LittleEndian, 4, false>(&u8s, 0, 4);
+        check_slice::<u8, LittleEndian, 4, false>(&u8s, 6, 4);
+        check_slice::<u8, BigEndian, 4, false>(&u8s, 0, 4);
+        check_slice::<u8, BigEndian, 32, true>(&u8s, 0, 32);
+        check_slice::<u8, LittleEndian, 1, false>(&u8s, 3, 1);
+        check_slice::<i8, LittleEndian, 4, false>(&i8s, 0, 4);
+        check_slice::<i8, BigEndian, 32, true>(&i8s, 0, 32);
+        check_slice::<u8, LittleEndian, 4, false>(&[], 0, 4);
+
+        let array: [u8; 3] = [1, 0xFF, 3];
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&array, &mut buf, 0).unwrap();
+        assert_eq!(hex::encode(&buf), "01000000ff00000003000000");
+        assert_eq!(FluentABI::<[u8; 3]>::decode(&buf, 0).unwrap(), array);
+
+        // Solidity decoding of byte words is still strict
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&vec![-1i8, 1], &mut buf, 0).unwrap();
+        assert_eq!(
+            SolidityABI::<Vec<i8>>::decode(&buf, 0).unwrap(),
+            vec![-1, 1]
+        );
+        buf[64] = 0;
+        assert!(SolidityABI::<Vec<i8>>::decode(&buf, 0).is_err());
+    }
+
+    #[test]
+    fn test_dense_int_slice_too_small() {
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&vec![1u64, 2, 3], &mut buf, 0).unwrap();
+        // Claim one more element than the data holds
+        buf[0] = 4;
+        assert!(matches!(
+            FluentABI::<Vec<u64>>::decode(&buf, 0),
+            Err(CodecError::Decoding(DecodingError::BufferTooSmall { .. }))
+        ));
+
+        let err = <u32 as Encoder<LittleEndian, 4, false>>::decode_slice(
+            &&[0u8; 8][..],
+            0,
+            usize::MAX,
+            4,
+        )
+        .unwrap_err();
+        assert!(matches!(err, CodecError::Decoding(DecodingError::Overflow)));
+    }
 }
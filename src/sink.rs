@@ -0,0 +1,439 @@
+use crate::{
+    alloc::{format, vec::Vec},
+    encoder::Encoder,
+    error::{CodecError, EncodingError},
+};
+use byteorder::ByteOrder;
+use bytes::{BufMut, BytesMut};
+
+/// Output of [`Encoder::encode`](crate::Encoder::encode).
+///
+/// Encoders write headers at given offsets and append dynamic data to the end, so a sink is a
+/// growable byte array: it's implemented for `BytesMut`, `Vec<u8>`, [`SliceSink`] over a fixed
+/// `&mut [u8]` and [`CountingSink`], that only measures the encoded size. An append-only `BufMut`
+/// can't go back to patch the headers, [`encode_to_buf_mut`] encodes into a scratch buffer and
+/// puts the result.
+pub trait EncodeSink {
+    /// Number of bytes written so far.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Grows or shrinks the written data to `new_len` bytes, new bytes are zeroed.
+    fn resize_zeroed(&mut self, new_len: usize) -> Result<(), CodecError>;
+
+    /// Overwrites already written bytes starting at `offset`.
+    fn write_at(&mut self, offset: usize, data: &[u8]) -> Result<(), CodecError>;
+
+    /// Overwrites `len` already written bytes starting at `offset` with `byte`.
+    fn fill_at(&mut self, offset: usize, len: usize, byte: u8) -> Result<(), CodecError> {
+        let chunk = [byte; 32];
+        let mut pos = offset;
+        while pos < offset + len {
+            let size = (offset + len - pos).min(chunk.len());
+            self.write_at(pos, &chunk[..size])?;
+            pos += size;
+        }
+        Ok(())
+    }
+
+    /// Reserves capacity for at least `additional` more bytes, if the sink can grow.
+    fn reserve(&mut self, _additional: usize) {}
+
+    /// Appends the data to the end.
+    fn append(&mut self, data: &[u8]) -> Result<(), CodecError> {
+        let offset = self.len();
+        self.resize_zeroed(offset + data.len())?;
+        self.write_at(offset, data)
+    }
+}
+
+fn out_of_bounds(offset: usize, size: usize, len: usize) -> CodecError {
+    CodecError::Encoding(EncodingError::BufferTooSmall {
+        required: offset + size,
+        available: len,
+        details: format!("can't write {} bytes at offset {}", size, offset),
+    })
+}
+
+impl EncodeSink for BytesMut {
+    fn len(&self) -> usize {
+        BytesMut::len(self)
+    }
+
+    fn resize_zeroed(&mut self, new_len: usize) -> Result<(), CodecError> {
+        self.resize(new_len, 0);
+        Ok(())
+    }
+
+    fn write_at(&mut self, offset: usize, data: &[u8]) -> Result<(), CodecError> {
+        let len = BytesMut::len(self);
+        self.get_mut(offset..offset + data.len())
+            .ok_or_else(|| out_of_bounds(offset, data.len(), len))?
+            .copy_from_slice(data);
+        Ok(())
+    }
+
+    fn fill_at(&mut self, offset: usize, len: usize, byte: u8) -> Result<(), CodecError> {
+        let buf_len = BytesMut::len(self);
+        self.get_mut(offset..offset + len)
+            .ok_or_else(|| out_of_bounds(offset, len, buf_len))?
+            .fill(byte);
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        BytesMut::reserve(self, additional);
+    }
+
+    fn append(&mut self, data: &[u8]) -> Result<(), CodecError> {
+        self.extend_from_slice(data);
+        Ok(())
+    }
+}
+
+impl EncodeSink for Vec<u8> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn resize_zeroed(&mut self, new_len: usize) -> Result<(), CodecError> {
+        self.resize(new_len, 0);
+        Ok(())
+    }
+
+    fn write_at(&mut self, offset: usize, data: &[u8]) -> Result<(), CodecError> {
+        let len = Vec::len(self);
+        self.get_mut(offset..offset + data.len())
+            .ok_or_else(|| out_of_bounds(offset, data.len(), len))?
+            .copy_from_slice(data);
+        Ok(())
+    }
+
+    fn fill_at(&mut self, offset: usize, len: usize, byte: u8) -> Result<(), CodecError> {
+        let buf_len = Vec::len(self);
+        self.get_mut(offset..offset + len)
+            .ok_or_else(|| out_of_bounds(offset, len, buf_len))?
+            .fill(byte);
+        Ok(())
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        Vec::reserve(self, additional);
+    }
+
+    fn append(&mut self, data: &[u8]) -> Result<(), CodecError> {
+        self.extend_from_slice(data);
+        Ok(())
+    }
+}
+
+/// Sink writing into a fixed slice, e.g. memory owned by an FFI caller. Encoding fails with
+/// [`EncodingError::BufferTooSmall`] if the value doesn't fit.
+pub struct SliceSink<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+}
+
+impl<'a> SliceSink<'a> {
+    pub fn new(buf: &'a mut [u8]) -> Self {
+        Self { buf, len: 0 }
+    }
+
+    /// The encoded bytes.
+    pub fn written(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+impl<'a> From<&'a mut [u8]> for SliceSink<'a> {
+    fn from(buf: &'a mut [u8]) -> Self {
+        Self::new(buf)
+    }
+}
+
+impl EncodeSink for SliceSink<'_> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn resize_zeroed(&mut self, new_len: usize) -> Result<(), CodecError> {
+        if new_len > self.buf.len() {
+            return Err(CodecError::Encoding(EncodingError::BufferTooSmall {
+                required: new_len,
+                available: self.buf.len(),
+                details: "the encoded value doesn't fit into the output slice".into(),
+            }));
+        }
+        if new_len > self.len {
+            // The slice may hold anything, encoders expect zeroed padding
+            self.buf[self.len..new_len].fill(0);
+        }
+        self.len = new_len;
+        Ok(())
+    }
+
+    fn write_at(&mut self, offset: usize, data: &[u8]) -> Result<(), CodecError> {
+        self.buf[..self.len]
+            .get_mut(offset..offset + data.len())
+            .ok_or_else(|| out_of_bounds(offset, data.len(), self.len))?
+            .copy_from_slice(data);
+        Ok(())
+    }
+
+    fn fill_at(&mut self, offset: usize, len: usize, byte: u8) -> Result<(), CodecError> {
+        self.buf[..self.len]
+            .get_mut(offset..offset + len)
+            .ok_or_else(|| out_of_bounds(offset, len, self.len))?
+            .fill(byte);
+        Ok(())
+    }
+}
+
+/// View of another sink starting at `base`, so that a nested value encodes its offsets relative to
+/// its own start while writing straight into the final buffer.
+pub struct SubSink<'a, S: EncodeSink + ?Sized> {
+    sink: &'a mut S,
+    base: usize,
+}
+
+impl<'a, S: EncodeSink + ?Sized> SubSink<'a, S> {
+    /// `base` must not be past the end of the sink.
+    pub fn new(sink: &'a mut S, base: usize) -> Self {
+        debug_assert!(base <= sink.len());
+        Self { sink, base }
+    }
+}
+
+impl<S: EncodeSink + ?Sized> EncodeSink for SubSink<'_, S> {
+    fn len(&self) -> usize {
+        self.sink.len() - self.base
+    }
+
+    fn resize_zeroed(&mut self, new_len: usize) -> Result<(), CodecError> {
+        self.sink.resize_zeroed(self.base + new_len)
+    }
+
+    fn write_at(&mut self, offset: usize, data: &[u8]) -> Result<(), CodecError> {
+        self.sink.write_at(self.base + offset, data)
+    }
+
+    fn fill_at(&mut self, offset: usize, len: usize, byte: u8) -> Result<(), CodecError> {
+        self.sink.fill_at(self.base + offset, len, byte)
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.sink.reserve(additional);
+    }
+
+    fn append(&mut self, data: &[u8]) -> Result<(), CodecError> {
+        self.sink.append(data)
+    }
+}
+
+/// Sink that discards the data and only counts the encoded bytes.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CountingSink {
+    len: usize,
+}
+
+impl CountingSink {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl EncodeSink for CountingSink {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn resize_zeroed(&mut self, new_len: usize) -> Result<(), CodecError> {
+        self.len = new_len;
+        Ok(())
+    }
+
+    fn write_at(&mut self, offset: usize, data: &[u8]) -> Result<(), CodecError> {
+        if offset + data.len() > self.len {
+            return Err(out_of_bounds(offset, data.len(), self.len));
+        }
+        Ok(())
+    }
+}
+
+/// Encodes the value at offset 0 into any `BufMut`. The value is encoded into a scratch buffer of
+/// its [`Encoder::encoded_len`] first, since the headers are patched after dynamic data is
+/// appended, then put into `out`.
+pub fn encode_to_buf_mut<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    value: &T,
+    out: &mut impl BufMut,
+) -> Result<(), CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let len = value.encoded_len();
+    if out.remaining_mut() < len {
+        return Err(CodecError::Encoding(EncodingError::BufferTooSmall {
+            required: len,
+            available: out.remaining_mut(),
+            details: "the encoded value doesn't fit into the output buffer".into(),
+        }));
+    }
+
+    let mut scratch = Vec::with_capacity(len);
+    value.encode(&mut scratch, 0)?;
+    out.put_slice(&scratch);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        alloc::{string::String, vec, vec::Vec},
+        encoder::{FluentABI, SolidityABI},
+    };
+    use alloy_primitives::{Address, U256};
+
+    type Value = (u32, Vec<String>, Address, U256);
+
+    fn value() -> Value {
+        (
+            7,
+            vec!["hello".into(), "world".into()],
+            Address::repeat_byte(0xAA),
+            U256::from(42),
+        )
+    }
+
+    fn fluent_value() -> (u64, Vec<Vec<u16>>, Option<u32>) {
+        (7, vec![vec![1, 2, 3], vec![], vec![4]], Some(5))
+    }
+
+    #[test]
+    fn test_vec_sink_matches_bytes_mut() {
+        let mut expected = BytesMut::new();
+        SolidityABI::encode(&value(), &mut expected, 0).unwrap();
+        let mut buf = Vec::new();
+        SolidityABI::encode(&value(), &mut buf, 0).unwrap();
+        assert_eq!(buf, expected.to_vec());
+
+        let mut expected = BytesMut::new();
+        FluentABI::encode(&fluent_value(), &mut expected, 0).unwrap();
+        let mut buf = Vec::new();
+        FluentABI::encode(&fluent_value(), &mut buf, 0).unwrap();
+        assert_eq!(buf, expected.to_vec());
+    }
+
+    #[test]
+    fn test_slice_sink() {
+        let mut expected = BytesMut::new();
+        SolidityABI::encode(&value(), &mut expected, 0).unwrap();
+
+        // Leftovers in the slice must not leak into the padding
+        let mut memory = [0xFFu8; 1024];
+        let mut sink = SliceSink::new(&mut memory);
+        SolidityABI::encode(&value(), &mut sink, 0).unwrap();
+        assert_eq!(sink.written(), &expected[..]);
+        assert_eq!(memory[expected.len()], 0xFF);
+
+        // A slice of the exact size is enough
+        let mut expected = BytesMut::new();
+        FluentABI::encode(&fluent_value(), &mut expected, 0).unwrap();
+        let mut memory = vec![0u8; expected.len()];
+        let mut sink = SliceSink::new(&mut memory);
+        FluentABI::encode(&fluent_value(), &mut sink, 0).unwrap();
+        assert_eq!(sink.written(), &expected[..]);
+
+        // A plain slice converts into a sink
+        let mut memory = [0u8; 4];
+        let mut sink = SliceSink::from(&mut memory[..]);
+        FluentABI::encode(&7u32, &mut sink, 0).unwrap();
+        assert_eq!(sink.written(), &[7, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_slice_sink_too_small() {
+        let mut expected = BytesMut::new();
+        SolidityABI::encode(&value(), &mut expected, 0).unwrap();
+
+        let mut memory = vec![0u8; expected.len() - 1];
+        let mut sink = SliceSink::new(&mut memory);
+        let err = SolidityABI::encode(&value(), &mut sink, 0).unwrap_err();
+        assert!(matches!(
+            err,
+            CodecError::Encoding(EncodingError::BufferTooSmall { .. })
+        ));
+
+        let mut memory = [0u8; 3];
+        let mut sink = SliceSink::new(&mut memory);
+        let err = FluentABI::encode(&1u32, &mut sink, 0).unwrap_err();
+        assert!(matches!(
+            err,
+            CodecError::Encoding(EncodingError::BufferTooSmall {
+                required: 4,
+                available: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_sub_sink() {
+        let mut expected = BytesMut::new();
+        SolidityABI::encode(&value(), &mut expected, 0).unwrap();
+
+        // Offsets are relative to the base, writes land after the prefix
+        let mut buf = vec![0xAB; 7];
+        let mut sink = SubSink::new(&mut buf, 7);
+        SolidityABI::encode(&value(), &mut sink, 0).unwrap();
+        assert_eq!(sink.len(), expected.len());
+        assert_eq!(&buf[..7], &[0xAB; 7]);
+        assert_eq!(&buf[7..], &expected[..]);
+    }
+
+    #[test]
+    fn test_encode_to_buf_mut() {
+        let mut expected = BytesMut::new();
+        SolidityABI::encode(&value(), &mut expected, 0).unwrap();
+
+        // The encoding lands after what the buffer already holds
+        let mut buf = vec![0xAB; 3];
+        SolidityABI::encode_to_buf_mut(&value(), &mut buf).unwrap();
+        assert_eq!(&buf[..3], &[0xAB; 3]);
+        assert_eq!(&buf[3..], &expected[..]);
+
+        let mut expected = BytesMut::new();
+        FluentABI::encode(&fluent_value(), &mut expected, 0).unwrap();
+        let mut memory = vec![0xFFu8; expected.len() + 1];
+        let mut out = &mut memory[..];
+        FluentABI::encode_to_buf_mut(&fluent_value(), &mut out).unwrap();
+        assert_eq!(out.len(), 1);
+        assert_eq!(&memory[..expected.len()], &expected[..]);
+
+        // A fixed `BufMut` that's too small fails instead of panicking
+        let mut memory = vec![0u8; expected.len() - 1];
+        let err = FluentABI::encode_to_buf_mut(&fluent_value(), &mut &mut memory[..]).unwrap_err();
+        assert!(matches!(
+            err,
+            CodecError::Encoding(EncodingError::BufferTooSmall { .. })
+        ));
+    }
+
+    #[test]
+    fn test_counting_sink() {
+        let mut expected = BytesMut::new();
+        SolidityABI::encode(&value(), &mut expected, 0).unwrap();
+        let mut sink = CountingSink::new();
+        SolidityABI::encode(&value(), &mut sink, 0).unwrap();
+        assert_eq!(sink.len(), expected.len());
+
+        let mut expected = BytesMut::new();
+        FluentABI::encode(&fluent_value(), &mut expected, 0).unwrap();
+        let mut sink = CountingSink::new();
+        FluentABI::encode(&fluent_value(), &mut sink, 0).unwrap();
+        assert_eq!(sink.len(), expected.len());
+    }
+}
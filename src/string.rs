@@ -0,0 +1,254 @@
+use crate::{
+    bytes::{bytes_encoded_len, encode_bytes, read_bytes, read_bytes_header},
+    encoder::Encoder,
+    error::{CodecError, DecodingError},
+    packed::{write_packed_right_padded, PackedEncoder},
+    sink::EncodeSink,
+};
+use alloc::{
+    borrow::Cow,
+    format,
+    string::{String, ToString},
+};
+use byteorder::ByteOrder;
+use bytes::Buf;
+use core::str::Utf8Error;
+
+/// Strings use exactly the same layout as `Bytes`:
+/// - Solidity mode: offset in the header, length and UTF-8 data in the tail
+/// - Fluent mode: offset and length in the header, UTF-8 data in the tail
+impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, { ALIGN }, { SOL_MODE }>
+    for String
+{
+    const HEADER_SIZE: usize = if SOL_MODE { 32 } else { size_of::<u32>() * 2 };
+    const IS_DYNAMIC: bool = true;
+
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        encode_bytes::<B, ALIGN, SOL_MODE>(self.as_bytes(), buf, offset)
+    }
+
+    fn encoded_len(&self) -> usize {
+        bytes_encoded_len::<ALIGN, SOL_MODE>(self.len())
+    }
+
+    /// Decode the string from the buffer.
+    /// Returns an error if the data is not valid UTF-8.
+    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+        let data = read_bytes::<B, ALIGN, SOL_MODE>(buf, offset)?;
+
+        String::from_utf8(data.to_vec()).map_err(|err| invalid_utf8(err.utf8_error()))
+    }
+
+    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        read_bytes_header::<B, ALIGN, SOL_MODE>(buf, offset)
+    }
+}
+
+/// Borrowed strings can only be encoded, decode into `String` instead.
+impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, { ALIGN }, { SOL_MODE }>
+    for &str
+{
+    const HEADER_SIZE: usize = <String as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+    const IS_DYNAMIC: bool = true;
+
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        encode_bytes::<B, ALIGN, SOL_MODE>(self.as_bytes(), buf, offset)
+    }
+
+    fn encoded_len(&self) -> usize {
+        bytes_encoded_len::<ALIGN, SOL_MODE>(self.len())
+    }
+
+    fn decode(_buf: &impl Buf, _offset: usize) -> Result<Self, CodecError> {
+        Err(CodecError::Decoding(DecodingError::InvalidData(
+            "can't decode into a borrowed &str, use decode_borrowed or decode into String instead"
+                .to_string(),
+        )))
+    }
+
+    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        read_bytes_header::<B, ALIGN, SOL_MODE>(buf, offset)
+    }
+}
+
+/// `Cow` strings are decoded into an owned `String`, `decode_borrowed` borrows them from the input
+/// instead.
+impl<'a, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, { ALIGN }, { SOL_MODE }>
+    for Cow<'a, str>
+{
+    const HEADER_SIZE: usize = <String as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+    const IS_DYNAMIC: bool = true;
+
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        encode_bytes::<B, ALIGN, SOL_MODE>(self.as_bytes(), buf, offset)
+    }
+
+    fn encoded_len(&self) -> usize {
+        bytes_encoded_len::<ALIGN, SOL_MODE>(self.len())
+    }
+
+    fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+        <String as Encoder<B, ALIGN, SOL_MODE>>::decode(buf, offset).map(Cow::Owned)
+    }
+
+    fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+        read_bytes_header::<B, ALIGN, SOL_MODE>(buf, offset)
+    }
+}
+
+pub(crate) fn invalid_utf8(err: Utf8Error) -> CodecError {
+    CodecError::Decoding(DecodingError::InvalidData(format!(
+        "invalid UTF-8 string: {}",
+        err
+    )))
+}
+
+impl PackedEncoder for String {
+    const PACKED_SIZE: Option<usize> = None;
+
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        self.as_str().encode_packed(buf)
+    }
+
+    fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        self.as_str().encode_packed_array_element(buf)
+    }
+}
+
+impl PackedEncoder for Cow<'_, str> {
+    const PACKED_SIZE: Option<usize> = None;
+
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        self.as_ref().encode_packed(buf)
+    }
+
+    fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        self.as_ref().encode_packed_array_element(buf)
+    }
+}
+
+impl PackedEncoder for &str {
+    const PACKED_SIZE: Option<usize> = None;
+
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        buf.append(self.as_bytes())
+    }
+
+    fn encode_packed_array_element(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        write_packed_right_padded(buf, self.as_bytes())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        encoder::{FluentABI, SolidityABI},
+        packed::SolidityPackedABI,
+    };
+    use alloy_sol_types::{sol_data, SolType, SolValue};
+    use byteorder::LE;
+    use bytes::BytesMut;
+    use hashbrown::HashMap;
+
+    #[test]
+    fn test_string_sol() {
+        let original = String::from("Hello, World!!");
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let encoded = buf.freeze();
+
+        let alloy_encoded = sol_data::String::abi_encode(&original);
+        assert_eq!(encoded.to_vec(), alloy_encoded);
+
+        let decoded = SolidityABI::<String>::decode(&encoded, 0).unwrap();
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn test_string_wasm() {
+        let original = String::from("Hello, World!!");
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        let encoded = buf.freeze();
+
+        assert_eq!(
+            hex::encode(&encoded),
+            "080000000e00000048656c6c6f2c20576f726c6421210000"
+        );
+
+        let decoded = FluentABI::<String>::decode(&encoded, 0).unwrap();
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn test_str_matches_string() {
+        let mut string_buf = BytesMut::new();
+        SolidityABI::encode(&(String::from("abc"), 7u32), &mut string_buf, 0).unwrap();
+
+        let mut str_buf = BytesMut::new();
+        SolidityABI::encode(&("abc", 7u32), &mut str_buf, 0).unwrap();
+
+        assert_eq!(string_buf, str_buf);
+        assert_eq!(str_buf.to_vec(), (String::from("abc"), 7u32).abi_encode());
+
+        let result = <&str as Encoder<LE, 4, false>>::decode(&&str_buf[..], 0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_string_invalid_utf8() {
+        let mut buf = BytesMut::new();
+        let invalid = alloy_primitives::Bytes::from_static(&[0xF0, 0x28, 0x8C, 0x28]);
+        FluentABI::encode(&invalid, &mut buf, 0).unwrap();
+
+        let result = FluentABI::<String>::decode(&buf, 0);
+        assert!(matches!(
+            result,
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&invalid, &mut buf, 0).unwrap();
+
+        let result = SolidityABI::<String>::decode(&buf, 0);
+        assert!(matches!(
+            result,
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+    }
+
+    #[test]
+    fn test_string_keys_map() {
+        let mut original = HashMap::new();
+        original.insert(String::from("bob"), 2u32);
+        original.insert(String::from("alice"), 1u32);
+        original.insert(String::from("a much longer key that spans two words"), 3u32);
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        let decoded = FluentABI::<HashMap<String, u32>>::decode(&buf, 0).unwrap();
+        assert_eq!(decoded, original);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let decoded = SolidityABI::<HashMap<String, u32>>::decode(&buf, 0).unwrap();
+        assert_eq!(decoded, original);
+    }
+
+    #[test]
+    fn test_string_packed() {
+        let value = (String::from("hello"), "world", vec![String::from("a")]);
+
+        let mut buf = BytesMut::new();
+        SolidityPackedABI::encode(&value, &mut buf).unwrap();
+
+        assert_eq!(
+            hex::encode(&buf),
+            "68656c6c6f776f726c64\
+             6100000000000000000000000000000000000000000000000000000000000000"
+        );
+    }
+}
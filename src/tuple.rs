@@ -1,17 +1,31 @@
 use crate::{
     alloc::string::ToString,
-    encoder::{align_up, read_u32_aligned, write_u32_aligned, Encoder},
+    encoder::{
+        align_up,
+        contiguous,
+        encode_zeroed,
+        encoded_tail_len,
+        ensure_buf_size,
+        read_u32_aligned,
+        write_u32_aligned,
+        zero_tail_len,
+        zeroed_len,
+        Encoder,
+    },
     error::{CodecError, DecodingError},
+    packed::PackedEncoder,
+    primitive::element_header_size,
+    sink::{EncodeSink, SubSink},
 };
 use byteorder::ByteOrder;
-use bytes::{Buf, BytesMut};
+use bytes::Buf;
 impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, { ALIGN }, { SOL_MODE }>
     for ()
 {
     const HEADER_SIZE: usize = 0;
     const IS_DYNAMIC: bool = false;
 
-    fn encode(&self, _buf: &mut BytesMut, _offset: usize) -> Result<(), CodecError> {
+    fn encode(&self, _buf: &mut impl EncodeSink, _offset: usize) -> Result<(), CodecError> {
         Ok(())
     }
 
@@ -29,48 +43,82 @@ impl<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, { ALI
 where
     T: Encoder<B, { ALIGN }, { SOL_MODE }>,
 {
-    const HEADER_SIZE: usize = align_up::<ALIGN>(T::HEADER_SIZE);
+    /// A dynamic tuple is appended to the end of the buffer, its header is the offset.
+    const HEADER_SIZE: usize = if T::IS_DYNAMIC {
+        if SOL_MODE {
+            WORD_SIZE
+        } else {
+            U32_SIZE
+        }
+    } else {
+        align_up::<ALIGN>(T::HEADER_SIZE)
+    };
     const IS_DYNAMIC: bool = T::IS_DYNAMIC;
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
-        let mut current_offset = offset;
-        let header_el_size = if SOL_MODE {
-            align_up::<ALIGN>(32)
-        } else {
-            align_up::<ALIGN>(4)
-        };
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
         if Self::IS_DYNAMIC {
-            let buf_len = buf.len();
-            let dynamic_offset = if buf_len == 0 {
-                header_el_size
-            } else {
-                buf_len
-            };
-            write_u32_aligned::<B, ALIGN>(buf, current_offset, dynamic_offset as u32);
-            current_offset += header_el_size;
+            ensure_buf_size(buf, offset + align_up::<ALIGN>(U32_SIZE))?;
+            let tail_offset = buf.len();
+            write_u32_aligned::<B, ALIGN>(buf, offset, tail_offset as u32)?;
+
+            // The tuple is encoded in place at the end of the buffer, its offsets are relative
+            // to its start
+            buf.resize_zeroed(tail_offset + element_header_size::<T, B, ALIGN, SOL_MODE>())?;
+            self.0.encode(&mut SubSink::new(buf, tail_offset), 0)?;
+        } else {
+            self.0.encode(buf, offset)?;
+        }
 
-            let aligned_header_size = align_up::<ALIGN>(T::HEADER_SIZE);
-            if buf_len < current_offset + aligned_header_size {
-                buf.resize(current_offset + aligned_header_size, 0);
-            }
-            let mut tmp = buf.split_off(current_offset);
+        Ok(())
+    }
 
-            self.0.encode(&mut tmp, 0)?;
-            buf.unsplit(tmp);
+    fn encoded_len(&self) -> usize {
+        let len = element_header_size::<T, B, ALIGN, SOL_MODE>()
+            + encoded_tail_len::<T, B, ALIGN, SOL_MODE>(&self.0);
+        if Self::IS_DYNAMIC {
+            // The offset word, then the tuple
+            align_up::<ALIGN>(U32_SIZE) + len
         } else {
-            self.0.encode(buf, current_offset)?;
+            len
         }
+    }
 
-        Ok(())
+    fn encode_zero(buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+        if !(SOL_MODE && Self::IS_DYNAMIC) {
+            return encode_zeroed::<Self, B, ALIGN, SOL_MODE>(buf, offset);
+        }
+
+        ensure_buf_size(buf, offset + WORD_SIZE)?;
+        let tail_offset = buf.len();
+        write_u32_aligned::<B, ALIGN>(buf, offset, tail_offset as u32)?;
+
+        buf.resize_zeroed(tail_offset + element_header_size::<T, B, ALIGN, SOL_MODE>())?;
+        T::encode_zero(&mut SubSink::new(buf, tail_offset), 0)
+    }
+
+    fn zero_encoded_len() -> usize {
+        if !(SOL_MODE && Self::IS_DYNAMIC) {
+            return zeroed_len::<Self, B, ALIGN, SOL_MODE>();
+        }
+
+        // The offset word, then the tuple
+        WORD_SIZE + T::zero_encoded_len()
     }
 
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
-        let chunk = if Self::IS_DYNAMIC {
-            let dynamic_offset = read_u32_aligned::<B, ALIGN>(&buf.chunk(), offset)? as usize;
-            &buf.chunk()[dynamic_offset..]
+        let data = contiguous(buf)?;
+        let chunk_offset = if Self::IS_DYNAMIC {
+            read_u32_aligned::<B, ALIGN>(&data.as_ref(), offset)? as usize
         } else {
-            &buf.chunk()[offset..]
+            offset
         };
+        let chunk = data.get(chunk_offset..).ok_or(CodecError::Decoding(
+            DecodingError::BufferTooSmall {
+                expected: chunk_offset,
+                found: data.len(),
+                msg: "buf too small to take tuple offset".to_string(),
+            },
+        ))?;
 
         Ok((T::decode(&chunk, 0)?,))
     }
@@ -80,6 +128,52 @@ where
     }
 }
 
+impl PackedEncoder for () {
+    const PACKED_SIZE: Option<usize> = Some(0);
+
+    fn encode_packed(&self, _buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        Ok(())
+    }
+}
+
+/// Packed tuples are the concatenation of their packed elements, like the arguments of
+/// `abi.encodePacked(a, b, ...)`.
+macro_rules! impl_packed_encoder_for_tuple {
+    ($($T:ident),+; $($idx:tt),+) => {
+        impl<$($T,)+> PackedEncoder for ($($T,)+)
+        where
+            $($T: PackedEncoder,)+
+        {
+            const PACKED_SIZE: Option<usize> = {
+                let mut size = Some(0);
+                $(
+                    size = match (size, $T::PACKED_SIZE) {
+                        (Some(size), Some(el_size)) => Some(size + el_size),
+                        _ => None,
+                    };
+                )+
+                size
+            };
+
+            fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+                $(
+                    self.$idx.encode_packed(buf)?;
+                )+
+                Ok(())
+            }
+        }
+    };
+}
+
+impl_packed_encoder_for_tuple!(T1; 0);
+impl_packed_encoder_for_tuple!(T1, T2; 0, 1);
+impl_packed_encoder_for_tuple!(T1, T2, T3; 0, 1, 2);
+impl_packed_encoder_for_tuple!(T1, T2, T3, T4; 0, 1, 2, 3);
+impl_packed_encoder_for_tuple!(T1, T2, T3, T4, T5; 0, 1, 2, 3, 4);
+impl_packed_encoder_for_tuple!(T1, T2, T3, T4, T5, T6; 0, 1, 2, 3, 4, 5);
+impl_packed_encoder_for_tuple!(T1, T2, T3, T4, T5, T6, T7; 0, 1, 2, 3, 4, 5, 6);
+impl_packed_encoder_for_tuple!(T1, T2, T3, T4, T5, T6, T7, T8; 0, 1, 2, 3, 4, 5, 6, 7);
+
 const WORD_SIZE: usize = 32;
 const U32_SIZE: usize = 4;
 
@@ -93,7 +187,10 @@ macro_rules! impl_encoder_for_tuple {
         where
             $($T: Encoder<B, {ALIGN}, $is_solidity>,)+
         {
-            const HEADER_SIZE: usize = {
+            /// A dynamic tuple is appended to the end of the buffer, its header is the offset.
+            const HEADER_SIZE: usize = if Self::IS_DYNAMIC {
+                if $is_solidity { WORD_SIZE } else { U32_SIZE }
+            } else {
                 let mut size = 0;
                 $(
                     size = align_up::<ALIGN>(size);
@@ -110,58 +207,85 @@ macro_rules! impl_encoder_for_tuple {
                 is_dynamic
             };
 
-            fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
+            fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
                 assert!(is_power_of_two(ALIGN), "ALIGN must be a power of two");
 
-                let mut current_offset = offset;
-
                 if Self::IS_DYNAMIC {
-                    let buf_len = buf.len();
-
-                    let dynamic_offset = if buf_len == 0 {
-                        if $is_solidity { WORD_SIZE } else { U32_SIZE }
-                    } else {
-                        buf_len
-                    };
-                    write_u32_aligned::<B, ALIGN>(buf, current_offset, dynamic_offset as u32);
-                    current_offset += if $is_solidity { WORD_SIZE } else { U32_SIZE };
-
+                    let offset_size = align_up::<ALIGN>(U32_SIZE);
+                    ensure_buf_size(buf, offset + offset_size)?;
+                    let tail_offset = buf.len();
+                    write_u32_aligned::<B, ALIGN>(buf, offset, tail_offset as u32)?;
+
+                    // The elements are encoded in place at the end of the buffer, their offsets are
+                    // relative to the tuple
+                    let header_size = 0 $(+ element_header_size::<$T, B, ALIGN, $is_solidity>())+;
+                    buf.resize_zeroed(tail_offset + header_size)?;
+                    let mut tail = SubSink::new(buf, tail_offset);
+                    let mut element_offset = 0;
+                    $(
+                        self.$idx.encode(&mut tail, element_offset)?;
+                        element_offset += element_header_size::<$T, B, ALIGN, $is_solidity>();
+                    )+
+                    debug_assert_eq!(element_offset, header_size);
+                } else {
+                    let mut element_offset = 0;
+                    $(
+                        self.$idx.encode(buf, offset + element_offset)?;
+                        element_offset += align_up::<ALIGN>($T::HEADER_SIZE);
+                    )+
+                    debug_assert_eq!(element_offset, Self::HEADER_SIZE);
+                }
 
-                    let aligned_header_size = {
-                        let mut size = 0;
-                        $(
-                            size += align_up::<ALIGN>($T::HEADER_SIZE);
-                        )+
-                        size
-                    };
+                Ok(())
+            }
 
+            fn encoded_len(&self) -> usize {
+                let len = 0 $(
+                    + element_header_size::<$T, B, ALIGN, $is_solidity>()
+                    + encoded_tail_len::<$T, B, ALIGN, $is_solidity>(&self.$idx)
+                )+;
+                if Self::IS_DYNAMIC {
+                    // The offset word, then the tuple
+                    align_up::<ALIGN>(U32_SIZE) + len
+                } else {
+                    len
+                }
+            }
 
+            /// The zero value of a tuple holds the zero values of its elements.
+            fn encode_zero(buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+                if !($is_solidity && Self::IS_DYNAMIC) {
+                    return encode_zeroed::<Self, B, ALIGN, $is_solidity>(buf, offset);
+                }
 
-                    if buf_len < current_offset + aligned_header_size {
-                        buf.resize(current_offset + aligned_header_size, 0);
-                    }
+                ensure_buf_size(buf, offset + WORD_SIZE)?;
+                let tail_offset = buf.len();
+                write_u32_aligned::<B, ALIGN>(buf, offset, tail_offset as u32)?;
 
+                // The elements are encoded in place, their offsets are relative to the tuple
+                let header_size = 0 $(+ element_header_size::<$T, B, ALIGN, $is_solidity>())+;
+                buf.resize_zeroed(tail_offset + header_size)?;
+                let mut tail = SubSink::new(buf, tail_offset);
+                let mut element_offset = 0;
+                $(
+                    $T::encode_zero(&mut tail, element_offset)?;
+                    element_offset += element_header_size::<$T, B, ALIGN, $is_solidity>();
+                )+
+                debug_assert_eq!(element_offset, header_size);
 
-                    let mut tmp = buf.split_off(current_offset);
-                    current_offset = 0;
-                    $(
+                Ok(())
+            }
 
-                        self.$idx.encode(&mut tmp, current_offset)?;
-                        current_offset += if $T::IS_DYNAMIC && $is_solidity {
-                           WORD_SIZE
-                        } else {
-                            align_up::<ALIGN>($T::HEADER_SIZE)
-                        };
-                    )+
-                    buf.unsplit(tmp);
-                } else {
-                    $(
-                        self.$idx.encode(buf, current_offset)?;
-                        current_offset += align_up::<ALIGN>($T::HEADER_SIZE);
-                    )+
+            fn zero_encoded_len() -> usize {
+                if !($is_solidity && Self::IS_DYNAMIC) {
+                    return zeroed_len::<Self, B, ALIGN, $is_solidity>();
                 }
 
-                Ok(())
+                // The offset word, then the tuple
+                WORD_SIZE $(
+                    + element_header_size::<$T, B, ALIGN, $is_solidity>()
+                    + zero_tail_len::<$T, B, ALIGN, $is_solidity>()
+                )+
             }
 
             fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
@@ -173,8 +297,9 @@ macro_rules! impl_encoder_for_tuple {
                     }));
                 }
 
+                let data = contiguous(buf)?;
                 let tmp = if Self::IS_DYNAMIC {
-                    let dynamic_offset = read_u32_aligned::<B, ALIGN>(&buf.chunk(), offset)? as usize;
+                    let dynamic_offset = read_u32_aligned::<B, ALIGN>(&data.as_ref(), offset)? as usize;
                     if buf.remaining() < dynamic_offset {
                        return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
                             expected: dynamic_offset,
@@ -182,27 +307,28 @@ macro_rules! impl_encoder_for_tuple {
                             msg: "buf too small to take dynamic offset".to_string(),
                         }));
                     }
-                    &buf.chunk()[dynamic_offset..]
+                    &data[dynamic_offset..]
                 } else {
-                    &buf.chunk()[offset..]
+                    &data[offset..]
                 };
 
-                let mut current_offset = 0;
-
-                Ok(($(
+                let mut element_offset = 0;
+                let value = ($(
                     {
-                        let value = $T::decode(&tmp, current_offset)?;
-                        current_offset += if $T::IS_DYNAMIC && $is_solidity{
-                            WORD_SIZE
-                        } else {
-                            align_up::<ALIGN>($T::HEADER_SIZE)
-                        };
+                        let value = $T::decode(&tmp, element_offset)?;
+                        element_offset += element_header_size::<$T, B, ALIGN, $is_solidity>();
                         value
                     },
-                )+))
+                )+);
+                debug_assert_eq!(
+                    element_offset,
+                    0 $(+ element_header_size::<$T, B, ALIGN, $is_solidity>())+
+                );
+
+                Ok(value)
             }
 
-            fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+            fn partial_decode(_buf: &impl Buf, _offset: usize) -> Result<(usize, usize), CodecError> {
                Ok((0,0))
             }
 
@@ -288,4 +414,30 @@ mod tests {
         let decoded = <Tuple as Encoder<LittleEndian, 4, false>>::decode(&encoded, 0).unwrap();
         assert_eq!(decoded, original);
     }
+
+    #[test]
+    fn test_decode_offset_out_of_bounds() {
+        use crate::{
+            encoder::{FluentABI, SolidityABI},
+            Codec,
+        };
+        use alloc::string::String;
+
+        #[derive(Codec, Debug, PartialEq)]
+        struct Named {
+            id: u32,
+            name: String,
+        }
+
+        // The offset word points past the end of the buffer
+        let mut sol = [0u8; 32];
+        sol[28..].copy_from_slice(&1000u32.to_be_bytes());
+        let mut wasm = [0u8; 4];
+        wasm.copy_from_slice(&1000u32.to_le_bytes());
+
+        assert!(SolidityABI::<(String,)>::decode(&&sol[..], 0).is_err());
+        assert!(FluentABI::<(String,)>::decode(&&wasm[..], 0).is_err());
+        assert!(SolidityABI::<Named>::decode(&&sol[..], 0).is_err());
+        assert!(FluentABI::<Named>::decode(&&wasm[..], 0).is_err());
+    }
 }
@@ -1,12 +1,23 @@
 use crate::{
     alloc::string::ToString,
-    bytes::{read_bytes, read_bytes_header, write_bytes_solidity, write_bytes_wasm},
-    encoder::{align_up, read_u32_aligned, write_u32_aligned, Encoder},
+    bytes::{read_bytes, read_bytes_header},
+    encoder::{
+        align_up,
+        append_slice,
+        contiguous,
+        encoded_tail_len,
+        read_u32_aligned,
+        write_u32_aligned,
+        Encoder,
+    },
     error::{CodecError, DecodingError},
+    packed::PackedEncoder,
+    primitive::element_header_size,
+    sink::EncodeSink,
 };
 use alloc::vec::Vec;
 use byteorder::ByteOrder;
-use bytes::{Buf, BytesMut};
+use bytes::Buf;
 
 /// We encode dynamic arrays as following:
 /// - header
@@ -23,25 +34,38 @@ use bytes::{Buf, BytesMut};
 ///   - length
 ///   - raw bytes of the vector
 ///
+/// Returns the size of the encoded vector body: element headers, `element_header_size` bytes
+/// apart, followed by the data of dynamic elements.
+fn elements_len<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(values: &[T]) -> usize
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    element_header_size::<T, B, ALIGN, SOL_MODE>() * values.len()
+        + values
+            .iter()
+            .map(encoded_tail_len::<T, B, ALIGN, SOL_MODE>)
+            .sum::<usize>()
+}
+
 /// Implementation for non-Solidity mode
 impl<T, B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, false> for Vec<T>
 where
-    T: Default + Sized + Encoder<B, { ALIGN }, false> + alloc::fmt::Debug,
+    T: Sized + Encoder<B, { ALIGN }, false>,
 {
     const HEADER_SIZE: usize = core::mem::size_of::<u32>() * 3;
     const IS_DYNAMIC: bool = true;
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
         let aligned_elem_size = align_up::<ALIGN>(4);
         let aligned_header_size = aligned_elem_size * 3;
 
         // Ensure buffer can store header
         if buf.len() < offset + aligned_header_size {
-            buf.resize(offset + aligned_header_size, 0);
+            buf.resize_zeroed(offset + aligned_header_size)?;
         }
 
         // Write length of the vector
-        write_u32_aligned::<B, ALIGN>(buf, offset, self.len() as u32);
+        write_u32_aligned::<B, ALIGN>(buf, offset, self.len() as u32)?;
 
         if self.is_empty() {
             // Write offset and size for empty vector
@@ -49,24 +73,33 @@ where
                 buf,
                 offset + aligned_elem_size,
                 aligned_header_size as u32,
-            );
-            write_u32_aligned::<B, ALIGN>(buf, offset + aligned_elem_size * 2, 0);
+            )?;
+            write_u32_aligned::<B, ALIGN>(buf, offset + aligned_elem_size * 2, 0)?;
             return Ok(());
         }
 
-        // Encode values
-        let mut value_encoder = BytesMut::zeroed(ALIGN.max(T::HEADER_SIZE) * self.len());
-        for (index, obj) in self.iter().enumerate() {
-            let elem_offset = ALIGN.max(T::HEADER_SIZE) * index;
-            obj.encode(&mut value_encoder, elem_offset)?;
-        }
-
-        let data = value_encoder.freeze();
-        write_bytes_wasm::<B, ALIGN>(buf, offset + aligned_elem_size, &data);
+        // Encode values at the end of the buffer
+        let data_offset = append_slice::<T, B, ALIGN, false>(
+            buf,
+            self,
+            element_header_size::<T, B, ALIGN, false>(),
+        )?;
+
+        // Write offset and size of the values, the size is known once they are written
+        write_u32_aligned::<B, ALIGN>(buf, offset + aligned_elem_size, data_offset as u32)?;
+        write_u32_aligned::<B, ALIGN>(
+            buf,
+            offset + aligned_elem_size * 2,
+            (buf.len() - data_offset) as u32,
+        )?;
 
         Ok(())
     }
 
+    fn encoded_len(&self) -> usize {
+        align_up::<ALIGN>(4) * 3 + elements_len::<T, B, ALIGN, false>(self)
+    }
+
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
         let aligned_header_el_size = align_up::<ALIGN>(4);
 
@@ -83,16 +116,13 @@ where
             return Ok(Vec::new());
         }
 
-        let mut result = Vec::with_capacity(data_len);
         let data = read_bytes::<B, ALIGN, false>(buf, offset + aligned_header_el_size)?;
-
-        for i in 0..data_len {
-            let elem_offset = i * align_up::<ALIGN>(T::HEADER_SIZE);
-            let value = T::decode(&data, elem_offset)?;
-            result.push(value);
-        }
-
-        Ok(result)
+        T::decode_slice(
+            &data,
+            0,
+            data_len,
+            element_header_size::<T, B, ALIGN, false>(),
+        )
     }
 
     fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
@@ -103,39 +133,37 @@ where
 // Implementation for Solidity mode
 impl<T, B: ByteOrder, const ALIGN: usize> Encoder<B, { ALIGN }, true> for Vec<T>
 where
-    T: Default + Sized + Encoder<B, { ALIGN }, true> + alloc::fmt::Debug,
+    T: Sized + Encoder<B, { ALIGN }, true>,
 {
     const HEADER_SIZE: usize = 32;
     const IS_DYNAMIC: bool = true;
 
-    fn encode(&self, buf: &mut BytesMut, offset: usize) -> Result<(), CodecError> {
+    fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
         // Ensure buffer can store header
         if buf.len() < offset + Self::HEADER_SIZE {
-            buf.resize(offset + Self::HEADER_SIZE, 0);
+            buf.resize_zeroed(offset + Self::HEADER_SIZE)?;
         }
 
         // Write offset
-        write_u32_aligned::<B, ALIGN>(buf, offset, buf.len() as u32);
+        write_u32_aligned::<B, ALIGN>(buf, offset, buf.len() as u32)?;
 
         if self.is_empty() {
             // Write length for empty vector
-            write_u32_aligned::<B, ALIGN>(buf, buf.len(), 0);
+            write_u32_aligned::<B, ALIGN>(buf, buf.len(), 0)?;
             return Ok(());
         }
 
-        // Encode values
-        let mut value_encoder = BytesMut::zeroed(32 * self.len());
-        for (index, obj) in self.iter().enumerate() {
-            let elem_offset = ALIGN.max(T::HEADER_SIZE) * index;
-            obj.encode(&mut value_encoder, elem_offset)?;
-        }
-
-        let data = value_encoder.freeze();
-        write_bytes_solidity::<B, ALIGN>(buf, offset + Self::HEADER_SIZE, &data, self.len() as u32);
+        // Write length, then encode values after it
+        write_u32_aligned::<B, ALIGN>(buf, buf.len(), self.len() as u32)?;
+        append_slice::<T, B, ALIGN, true>(buf, self, element_header_size::<T, B, ALIGN, true>())?;
 
         Ok(())
     }
 
+    fn encoded_len(&self) -> usize {
+        Self::HEADER_SIZE + align_up::<ALIGN>(4) + elements_len::<T, B, ALIGN, true>(self)
+    }
+
     fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
         let data_offset = read_u32_aligned::<B, ALIGN>(buf, offset)?;
         let data_len = read_u32_aligned::<B, ALIGN>(buf, data_offset as usize)? as usize;
@@ -144,16 +172,13 @@ where
             return Ok(Vec::new());
         }
 
-        let mut result = Vec::with_capacity(data_len);
-        let chunk = &buf.chunk()[(data_offset + 32) as usize..];
-
-        for i in 0..data_len {
-            let elem_offset = i * align_up::<ALIGN>(T::HEADER_SIZE);
-            let value = T::decode(&chunk, elem_offset)?;
-            result.push(value);
-        }
-
-        Ok(result)
+        let chunk = &contiguous(buf)?[(data_offset + 32) as usize..];
+        T::decode_slice(
+            &chunk,
+            0,
+            data_len,
+            element_header_size::<T, B, ALIGN, true>(),
+        )
     }
 
     fn partial_decode(buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
@@ -161,11 +186,86 @@ where
     }
 }
 
+/// In packed mode vector elements are padded to 32 bytes and there is no length prefix.
+impl<T: PackedEncoder> PackedEncoder for Vec<T> {
+    const PACKED_SIZE: Option<usize> = None;
+
+    fn encode_packed(&self, buf: &mut impl EncodeSink) -> Result<(), CodecError> {
+        for item in self.iter() {
+            item.encode_packed_array_element(buf)?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::encoder::{FluentABI, SolidityABI};
     use byteorder::{BigEndian, LittleEndian};
     use bytes::{Bytes, BytesMut};
+    use core::num::NonZeroU32;
+    use hashbrown::HashMap;
+
+    /// Has no `Default`, `Clone` or `Debug`, containers must decode it without them.
+    #[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+    struct Id(NonZeroU32);
+
+    impl<B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> Encoder<B, ALIGN, SOL_MODE> for Id {
+        const HEADER_SIZE: usize = <u32 as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE;
+        const IS_DYNAMIC: bool = false;
+
+        fn encode(&self, buf: &mut impl EncodeSink, offset: usize) -> Result<(), CodecError> {
+            <u32 as Encoder<B, ALIGN, SOL_MODE>>::encode(&self.0.get(), buf, offset)
+        }
+
+        fn decode(buf: &impl Buf, offset: usize) -> Result<Self, CodecError> {
+            let value = <u32 as Encoder<B, ALIGN, SOL_MODE>>::decode(buf, offset)?;
+            NonZeroU32::new(value)
+                .map(Id)
+                .ok_or(CodecError::Decoding(DecodingError::InvalidData(
+                    "zero id".to_string(),
+                )))
+        }
+
+        fn partial_decode(_buf: &impl Buf, offset: usize) -> Result<(usize, usize), CodecError> {
+            Ok((offset, <Self as Encoder<B, ALIGN, SOL_MODE>>::HEADER_SIZE))
+        }
+    }
+
+    fn id(value: u32) -> Id {
+        Id(NonZeroU32::new(value).unwrap())
+    }
+
+    type Ids = (Vec<Id>, [Id; 2], Option<Id>, HashMap<Id, Vec<Id>>);
+
+    fn ids() -> Ids {
+        (
+            vec![id(1), id(2), id(3)],
+            [id(4), id(5)],
+            Some(id(6)),
+            HashMap::from([(id(7), vec![id(8)]), (id(9), vec![])]),
+        )
+    }
+
+    #[test]
+    fn test_decode_without_default() {
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&ids(), &mut buf, 0).unwrap();
+        assert!(SolidityABI::<Ids>::decode(&buf, 0).unwrap() == ids());
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&ids(), &mut buf, 0).unwrap();
+        assert!(FluentABI::<Ids>::decode(&buf, 0).unwrap() == ids());
+
+        // A failing element fails the whole vector
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&vec![1u32, 0, 3], &mut buf, 0).unwrap();
+        assert!(matches!(
+            FluentABI::<Vec<Id>>::decode(&buf, 0),
+            Err(CodecError::Decoding(DecodingError::InvalidData(_)))
+        ));
+    }
 
     #[test]
     fn test_empty_vec_u32() {
@@ -0,0 +1,801 @@
+use crate::{
+    alloc::{format, string::ToString},
+    borrowed::DecodeBorrowed,
+    bytes::read_bytes_header,
+    encoder::{align_up, read_u32_aligned, Encoder},
+    error::{CodecError, DecodingError},
+    hash::{encode_key, read_map_layout, read_set_layout},
+    primitive::element_header_size,
+};
+use byteorder::{ByteOrder, BE, LE};
+use core::{cmp::Ordering, marker::PhantomData, ops::Range};
+
+/// Read-only view over an encoded map, that decodes entries on demand instead of decoding the
+/// whole map.
+///
+/// Lookups expect keys sorted by `Ord`, like `HashMap` and `BTreeMap` encode them, and are a
+/// binary search that decodes `O(log n)` keys and only the matching value. Maps encoded in
+/// another order (`IndexMap`) can only be iterated, lookups may miss their keys. Use
+/// [`ByteOrderedMapView`] for [`ByteOrdered`](crate::ByteOrdered) maps.
+pub struct MapView<'a, K, V, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> {
+    length: usize,
+    keys: &'a [u8],
+    values: &'a [u8],
+    _phantom: PhantomData<(K, V, B)>,
+}
+
+pub type FluentMapView<'a, K, V> = MapView<'a, K, V, LE, 4, false>;
+pub type SolidityMapView<'a, K, V> = MapView<'a, K, V, BE, 32, true>;
+
+impl<'a, K, V, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    MapView<'a, K, V, B, ALIGN, SOL_MODE>
+where
+    K: Encoder<B, { ALIGN }, { SOL_MODE }>,
+    V: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    /// Creates a view over the map encoded at `offset`. Only the map header is read.
+    pub fn new(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        let (length, keys_range, values_range) =
+            read_map_layout::<B, ALIGN, SOL_MODE>(&buf, offset)?;
+        let keys = &buf[keys_range];
+        let values = &buf[values_range];
+
+        check_elements_size(keys, length, element_header_size::<K, B, ALIGN, SOL_MODE>())?;
+        check_elements_size(
+            values,
+            length,
+            element_header_size::<V, B, ALIGN, SOL_MODE>(),
+        )?;
+
+        Ok(Self {
+            length,
+            keys,
+            values,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Iterates over entries in the encoded (sorted) order.
+    pub fn iter(&self) -> impl Iterator<Item = Result<(K, V), CodecError>> + '_ {
+        (0..self.length).map(|index| Ok((self.key_at(index)?, self.value_at(index)?)))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = Result<K, CodecError>> + '_ {
+        (0..self.length).map(|index| self.key_at(index))
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = Result<V, CodecError>> + '_ {
+        (0..self.length).map(|index| self.value_at(index))
+    }
+
+    fn key_at(&self, index: usize) -> Result<K, CodecError> {
+        K::decode(
+            &self.keys,
+            element_header_size::<K, B, ALIGN, SOL_MODE>() * index,
+        )
+    }
+
+    fn value_at(&self, index: usize) -> Result<V, CodecError> {
+        V::decode(
+            &self.values,
+            element_header_size::<V, B, ALIGN, SOL_MODE>() * index,
+        )
+    }
+}
+
+impl<'a, K, V, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    MapView<'a, K, V, B, ALIGN, SOL_MODE>
+where
+    K: Encoder<B, { ALIGN }, { SOL_MODE }> + Ord,
+    V: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    /// Returns the value for the key, if the map contains it.
+    pub fn get(&self, key: &K) -> Result<Option<V>, CodecError> {
+        match self.find(key)? {
+            Some(index) => self.value_at(index).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> Result<bool, CodecError> {
+        Ok(self.find(key)?.is_some())
+    }
+
+    fn find(&self, key: &K) -> Result<Option<usize>, CodecError> {
+        binary_search(self.length, |index| Ok(self.key_at(index)?.cmp(key)))
+    }
+}
+
+/// Read-only view over an encoded [`ByteOrdered`](crate::ByteOrdered) map.
+///
+/// Its keys are sorted by their encoded bytes, so a lookup encodes the key and compares it with
+/// the `O(log n)` re-encoded keys of a binary search.
+pub struct ByteOrderedMapView<'a, K, V, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    MapView<'a, K, V, B, ALIGN, SOL_MODE>,
+);
+
+pub type FluentByteOrderedMapView<'a, K, V> = ByteOrderedMapView<'a, K, V, LE, 4, false>;
+pub type SolidityByteOrderedMapView<'a, K, V> = ByteOrderedMapView<'a, K, V, BE, 32, true>;
+
+impl<'a, K, V, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    ByteOrderedMapView<'a, K, V, B, ALIGN, SOL_MODE>
+where
+    K: Encoder<B, { ALIGN }, { SOL_MODE }>,
+    V: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    /// Creates a view over the map encoded at `offset`. Only the map header is read.
+    pub fn new(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        MapView::new(buf, offset).map(Self)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the value for the key, if the map contains it.
+    pub fn get(&self, key: &K) -> Result<Option<V>, CodecError> {
+        match self.find(key)? {
+            Some(index) => self.0.value_at(index).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> Result<bool, CodecError> {
+        Ok(self.find(key)?.is_some())
+    }
+
+    /// Iterates over entries in the encoded order.
+    pub fn iter(&self) -> impl Iterator<Item = Result<(K, V), CodecError>> + '_ {
+        self.0.iter()
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = Result<K, CodecError>> + '_ {
+        self.0.keys()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = Result<V, CodecError>> + '_ {
+        self.0.values()
+    }
+
+    fn find(&self, key: &K) -> Result<Option<usize>, CodecError> {
+        let encoded = encode_key::<K, B, ALIGN, SOL_MODE>(key)?;
+        binary_search(self.0.length, |index| {
+            Ok(encode_key::<K, B, ALIGN, SOL_MODE>(&self.0.key_at(index)?)?.cmp(&encoded))
+        })
+    }
+}
+
+/// Read-only view over an encoded set, that decodes elements on demand instead of decoding the
+/// whole set.
+///
+/// Lookups expect elements sorted by `Ord`, like `HashSet` and `BTreeSet` encode them, and are a
+/// binary search that decodes `O(log n)` elements. Sets encoded in another order (`IndexSet`)
+/// can only be iterated. Use [`ByteOrderedSetView`] for [`ByteOrdered`](crate::ByteOrdered) sets.
+pub struct SetView<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> {
+    length: usize,
+    elements: &'a [u8],
+    _phantom: PhantomData<(T, B)>,
+}
+
+pub type FluentSetView<'a, T> = SetView<'a, T, LE, 4, false>;
+pub type SoliditySetView<'a, T> = SetView<'a, T, BE, 32, true>;
+
+impl<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    SetView<'a, T, B, ALIGN, SOL_MODE>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    /// Creates a view over the set encoded at `offset`. Only the set header is read.
+    pub fn new(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        let (length, elements_range) = read_set_layout::<B, ALIGN, SOL_MODE>(&buf, offset)?;
+        let elements = &buf[elements_range];
+
+        check_elements_size(
+            elements,
+            length,
+            element_header_size::<T, B, ALIGN, SOL_MODE>(),
+        )?;
+
+        Ok(Self {
+            length,
+            elements,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Iterates over elements in the encoded (sorted) order.
+    pub fn iter(&self) -> impl Iterator<Item = Result<T, CodecError>> + '_ {
+        (0..self.length).map(|index| self.element_at(index))
+    }
+
+    fn element_at(&self, index: usize) -> Result<T, CodecError> {
+        T::decode(
+            &self.elements,
+            element_header_size::<T, B, ALIGN, SOL_MODE>() * index,
+        )
+    }
+}
+
+impl<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    SetView<'a, T, B, ALIGN, SOL_MODE>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }> + Ord,
+{
+    pub fn contains(&self, value: &T) -> Result<bool, CodecError> {
+        Ok(binary_search(self.length, |index| Ok(self.element_at(index)?.cmp(value)))?.is_some())
+    }
+}
+
+/// Read-only view over an encoded [`ByteOrdered`](crate::ByteOrdered) set, whose elements are
+/// sorted by their encoded bytes.
+pub struct ByteOrderedSetView<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    SetView<'a, T, B, ALIGN, SOL_MODE>,
+);
+
+pub type FluentByteOrderedSetView<'a, T> = ByteOrderedSetView<'a, T, LE, 4, false>;
+pub type SolidityByteOrderedSetView<'a, T> = ByteOrderedSetView<'a, T, BE, 32, true>;
+
+impl<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    ByteOrderedSetView<'a, T, B, ALIGN, SOL_MODE>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    /// Creates a view over the set encoded at `offset`. Only the set header is read.
+    pub fn new(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        SetView::new(buf, offset).map(Self)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn contains(&self, value: &T) -> Result<bool, CodecError> {
+        let encoded = encode_key::<T, B, ALIGN, SOL_MODE>(value)?;
+        let found = binary_search(self.0.length, |index| {
+            Ok(encode_key::<T, B, ALIGN, SOL_MODE>(&self.0.element_at(index)?)?.cmp(&encoded))
+        })?;
+        Ok(found.is_some())
+    }
+
+    /// Iterates over elements in the encoded order.
+    pub fn iter(&self) -> impl Iterator<Item = Result<T, CodecError>> + '_ {
+        self.0.iter()
+    }
+}
+
+/// Read-only view over an encoded `Vec<T>`, that decodes elements on demand instead of decoding
+/// the whole vector.
+pub struct VecView<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool> {
+    /// Encoded elements, offsets of dynamic elements are relative to its start
+    data: &'a [u8],
+    /// Index of the first element of the view
+    start: usize,
+    length: usize,
+    _phantom: PhantomData<(T, B)>,
+}
+
+pub type FluentVecView<'a, T> = VecView<'a, T, LE, 4, false>;
+pub type SolidityVecView<'a, T> = VecView<'a, T, BE, 32, true>;
+
+impl<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    VecView<'a, T, B, ALIGN, SOL_MODE>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    /// Creates a view over the vector encoded at `offset`. Only the vector header is read.
+    pub fn new(buf: &'a [u8], offset: usize) -> Result<Self, CodecError> {
+        let (length, data) = if SOL_MODE {
+            // The header is an offset of the length, elements follow the length
+            let (data_offset, length) = read_bytes_header::<B, ALIGN, true>(&buf, offset)?;
+            (length, &buf[data_offset + 32..])
+        } else {
+            let length = read_u32_aligned::<B, ALIGN>(&buf, offset)? as usize;
+            let (data_offset, data_length) =
+                read_bytes_header::<B, ALIGN, false>(&buf, offset + align_up::<ALIGN>(4))?;
+            let data_end = data_offset
+                .checked_add(data_length)
+                .ok_or(CodecError::Decoding(DecodingError::Overflow))?;
+            if buf.len() < data_end {
+                return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+                    expected: data_end,
+                    found: buf.len(),
+                    msg: "Not enough data for the encoded elements".to_string(),
+                }));
+            }
+            (length, &buf[data_offset..data_end])
+        };
+
+        check_elements_size(data, length, element_header_size::<T, B, ALIGN, SOL_MODE>())?;
+
+        Ok(Self {
+            data,
+            start: 0,
+            length,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Encoded elements of the whole vector.
+    pub(crate) fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Decodes the element at `index`, or returns `None` if it's out of bounds.
+    pub fn get(&self, index: usize) -> Result<Option<T>, CodecError> {
+        if index >= self.length {
+            return Ok(None);
+        }
+        self.element_at(index).map(Some)
+    }
+
+    /// Returns a view over a sub-range of elements, or `None` if it's out of bounds. Nothing is
+    /// decoded.
+    pub fn slice(&self, range: Range<usize>) -> Option<Self> {
+        if range.start > range.end || range.end > self.length {
+            return None;
+        }
+
+        Some(Self {
+            data: self.data,
+            start: self.start + range.start,
+            length: range.end - range.start,
+            _phantom: PhantomData,
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Result<T, CodecError>> + '_ {
+        (0..self.length).map(|index| self.element_at(index))
+    }
+
+    fn element_at(&self, index: usize) -> Result<T, CodecError> {
+        T::decode(
+            &self.data,
+            element_header_size::<T, B, ALIGN, SOL_MODE>() * (self.start + index),
+        )
+    }
+}
+
+impl<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>
+    VecView<'a, T, B, ALIGN, SOL_MODE>
+where
+    T: DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }>,
+{
+    /// Like [`Self::get`], but the element borrows its data from the input buffer.
+    pub fn get_borrowed(&self, index: usize) -> Result<Option<T>, CodecError> {
+        if index >= self.length {
+            return Ok(None);
+        }
+        borrowed_element_at::<T, B, ALIGN, SOL_MODE>(self.data, self.start + index).map(Some)
+    }
+
+    /// Like [`Self::iter`], but elements borrow their data from the input buffer.
+    pub fn iter_borrowed(&self) -> impl Iterator<Item = Result<T, CodecError>> + 'a {
+        let data = self.data;
+        (self.start..self.start + self.length)
+            .map(move |index| borrowed_element_at::<T, B, ALIGN, SOL_MODE>(data, index))
+    }
+}
+
+fn borrowed_element_at<'a, T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    data: &'a [u8],
+    index: usize,
+) -> Result<T, CodecError>
+where
+    T: DecodeBorrowed<'a, B, { ALIGN }, { SOL_MODE }>,
+{
+    T::decode_borrowed(data, element_header_size::<T, B, ALIGN, SOL_MODE>() * index)
+}
+
+/// Decodes a single element of the vector encoded at `offset`, without decoding the others.
+pub fn decode_element<T, B: ByteOrder, const ALIGN: usize, const SOL_MODE: bool>(
+    buf: &[u8],
+    offset: usize,
+    index: usize,
+) -> Result<T, CodecError>
+where
+    T: Encoder<B, { ALIGN }, { SOL_MODE }>,
+{
+    let view = VecView::<T, B, ALIGN, SOL_MODE>::new(buf, offset)?;
+
+    view.get(index)?.ok_or_else(|| {
+        CodecError::Decoding(DecodingError::InvalidData(format!(
+            "index {} is out of bounds for a vector of length {}",
+            index,
+            view.len()
+        )))
+    })
+}
+
+/// Checks that the buffer holds headers of all elements, so element offsets can't overflow.
+fn check_elements_size(data: &[u8], length: usize, elem_size: usize) -> Result<(), CodecError> {
+    let expected = length
+        .checked_mul(elem_size)
+        .ok_or(CodecError::Decoding(DecodingError::Overflow))?;
+
+    if data.len() < expected {
+        return Err(CodecError::Decoding(DecodingError::BufferTooSmall {
+            expected,
+            found: data.len(),
+            msg: "Not enough data for the encoded elements".to_string(),
+        }));
+    }
+
+    Ok(())
+}
+
+/// Searches the sorted elements, `compare` orders the element at an index to the searched value.
+fn binary_search(
+    length: usize,
+    compare: impl Fn(usize) -> Result<Ordering, CodecError>,
+) -> Result<Option<usize>, CodecError> {
+    let (mut low, mut high) = (0, length);
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+        match compare(mid)? {
+            Ordering::Less => low = mid + 1,
+            Ordering::Greater => high = mid,
+            Ordering::Equal => return Ok(Some(mid)),
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        encoder::{FluentABI, SolidityABI},
+        ByteOrdered,
+    };
+    use alloc::{string::String, vec::Vec};
+    use alloy_primitives::{Address, U256};
+    use bytes::BytesMut;
+    use hashbrown::{HashMap, HashSet};
+
+    fn lookup_table() -> HashMap<Address, U256> {
+        (1..=100u8)
+            .map(|i| (Address::repeat_byte(i), U256::from(i) * U256::from(1000)))
+            .collect()
+    }
+
+    #[test]
+    fn test_map_view_sol() {
+        let original = lookup_table();
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+
+        let view = SolidityMapView::<Address, U256>::new(&buf, 0).unwrap();
+        assert_eq!(view.len(), 100);
+
+        for (key, value) in original.iter() {
+            assert_eq!(view.get(key).unwrap(), Some(*value));
+            assert!(view.contains_key(key).unwrap());
+        }
+        assert_eq!(view.get(&Address::ZERO).unwrap(), None);
+        assert!(!view.contains_key(&Address::repeat_byte(0xff)).unwrap());
+
+        let mut expected: Vec<_> = original.into_iter().collect();
+        expected.sort();
+        let entries: Vec<_> = view.iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(entries, expected);
+    }
+
+    #[test]
+    fn test_map_view_wasm_dynamic_values() {
+        let original: HashMap<u32, String> = (0..50u32)
+            .map(|i| (i * 2, "value ".repeat(i as usize)))
+            .collect();
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+
+        let view = FluentMapView::<u32, String>::new(&buf, 0).unwrap();
+        assert_eq!(view.len(), 50);
+        assert_eq!(view.get(&20).unwrap(), Some("value ".repeat(10)));
+        assert_eq!(view.get(&21).unwrap(), None);
+        assert_eq!(view.get(&100).unwrap(), None);
+
+        let keys: Vec<u32> = view.keys().collect::<Result<_, _>>().unwrap();
+        assert_eq!(keys, (0..50u32).map(|i| i * 2).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_map_view_nested() {
+        let original = (
+            7u32,
+            HashMap::from([
+                (String::from("b"), vec![1u32, 2]),
+                (String::from("a"), vec![]),
+            ]),
+        );
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+
+        // The map is the second element of the tuple, its offset is relative to the tuple start
+        let tuple_start = read_u32_aligned::<BE, 32>(&&buf[..], 0).unwrap() as usize;
+        let view = SolidityMapView::<String, Vec<u32>>::new(&buf[tuple_start..], 32).unwrap();
+
+        assert_eq!(view.get(&String::from("b")).unwrap(), Some(vec![1, 2]));
+        assert_eq!(view.get(&String::from("a")).unwrap(), Some(vec![]));
+        assert_eq!(view.get(&String::from("c")).unwrap(), None);
+    }
+
+    #[test]
+    fn test_empty_map_view() {
+        let original: HashMap<u32, u32> = HashMap::new();
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let view = SolidityMapView::<u32, u32>::new(&buf, 0).unwrap();
+        assert!(view.is_empty());
+        assert_eq!(view.get(&1).unwrap(), None);
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        let view = FluentMapView::<u32, u32>::new(&buf, 0).unwrap();
+        assert!(view.is_empty());
+        assert_eq!(view.iter().count(), 0);
+    }
+
+    #[test]
+    fn test_set_view() {
+        let original: HashSet<u64> = (0..64u64).map(|i| i * i).collect();
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        let view = FluentSetView::<u64>::new(&buf, 0).unwrap();
+        assert_eq!(view.len(), 64);
+        assert!(view.contains(&49).unwrap());
+        assert!(!view.contains(&50).unwrap());
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let view = SoliditySetView::<u64>::new(&buf, 0).unwrap();
+        assert!(view.contains(&3969).unwrap());
+        assert!(!view.contains(&3970).unwrap());
+
+        let elements: Vec<u64> = view.iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(elements, (0..64u64).map(|i| i * i).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_byte_ordered_map_view() {
+        // Negative keys are encoded after positive ones, unlike their `Ord` order
+        let original = ByteOrdered(HashMap::from([(-7i32, 70u32), (-1, 10), (5, 50)]));
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        let view = FluentByteOrderedMapView::<i32, u32>::new(&buf, 0).unwrap();
+        assert_eq!(view.len(), 3);
+        for (key, value) in original.iter() {
+            assert_eq!(view.get(key).unwrap(), Some(*value));
+        }
+        assert_eq!(view.get(&0).unwrap(), None);
+        let keys: Vec<i32> = view.keys().collect::<Result<_, _>>().unwrap();
+        assert_eq!(keys, vec![5, -7, -1]);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let view = SolidityByteOrderedMapView::<i32, u32>::new(&buf, 0).unwrap();
+        for (key, value) in original.iter() {
+            assert_eq!(view.get(key).unwrap(), Some(*value));
+            assert!(view.contains_key(key).unwrap());
+        }
+        assert!(!view.contains_key(&-2).unwrap());
+
+        let set = ByteOrdered(HashSet::from([
+            String::from("b"),
+            String::from("aa"),
+            String::from("c"),
+        ]));
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&set, &mut buf, 0).unwrap();
+        let view = FluentByteOrderedSetView::<String>::new(&buf, 0).unwrap();
+        for value in set.iter() {
+            assert!(view.contains(value).unwrap());
+        }
+        assert!(!view.contains(&String::from("a")).unwrap());
+    }
+
+    #[cfg(feature = "indexmap")]
+    #[test]
+    fn test_index_map_view_iter() {
+        use indexmap::IndexMap;
+
+        // Index maps are encoded in insertion order, the view only iterates them
+        let original: IndexMap<u32, u64> = [(3, 30), (1, 10), (2, 20)].into_iter().collect();
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+
+        let view = SolidityMapView::<u32, u64>::new(&buf, 0).unwrap();
+        let entries: Vec<_> = view.iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(entries, vec![(3, 30), (1, 10), (2, 20)]);
+    }
+
+    #[test]
+    fn test_map_view_truncated() {
+        let original = lookup_table();
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+
+        let result = FluentMapView::<Address, U256>::new(&buf[..buf.len() - 1], 0);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_vec_view_static() {
+        let original: Vec<u64> = (0..100).map(|i| i * 3).collect();
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        let view = FluentVecView::<u64>::new(&buf, 0).unwrap();
+        assert_eq!(view.len(), 100);
+        assert_eq!(view.get(10).unwrap(), Some(30));
+        assert_eq!(view.get(100).unwrap(), None);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let view = SolidityVecView::<u64>::new(&buf, 0).unwrap();
+        assert_eq!(view.get(99).unwrap(), Some(297));
+
+        let slice = view.slice(10..13).unwrap();
+        assert_eq!(slice.len(), 3);
+        assert_eq!(slice.get(0).unwrap(), Some(30));
+        assert_eq!(slice.get(3).unwrap(), None);
+        let elements: Vec<u64> = slice.iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(elements, [30, 33, 36]);
+
+        // Slices of slices are relative to the slice
+        let elements: Vec<u64> = slice
+            .slice(1..3)
+            .unwrap()
+            .iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(elements, [33, 36]);
+
+        assert!(view.slice(99..101).is_none());
+        assert!(view.slice(100..100).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_vec_view_dynamic() {
+        let original = vec![
+            String::from("a"),
+            String::new(),
+            String::from("a string that doesn't fit into a single word"),
+        ];
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        let view = FluentVecView::<String>::new(&buf, 0).unwrap();
+        assert_eq!(view.get(2).unwrap().as_ref(), Some(&original[2]));
+        let elements: Vec<String> = view.iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(elements, original);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let view = SolidityVecView::<String>::new(&buf, 0).unwrap();
+        let elements: Vec<String> = view
+            .slice(1..3)
+            .unwrap()
+            .iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(elements, original[1..]);
+
+        // Nested vectors
+        let original = vec![vec![1u32, 2], vec![], vec![3]];
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        let view = SolidityVecView::<Vec<u32>>::new(&buf, 0).unwrap();
+        assert_eq!(view.get(0).unwrap(), Some(vec![1, 2]));
+        assert_eq!(view.get(2).unwrap(), Some(vec![3]));
+    }
+
+    #[test]
+    fn test_vec_view_composite_elements() {
+        use crate::Codec;
+        use alloy_primitives::Bytes;
+
+        #[derive(Codec, Clone, Debug, PartialEq)]
+        struct Entry {
+            id: u32,
+            data: Bytes,
+        }
+
+        let tuples = vec![
+            (1u32, Bytes::from_static(b"one")),
+            (2, Bytes::new()),
+            (3, Bytes::from(vec![0xAB; 40])),
+        ];
+        let entries: Vec<Entry> = tuples
+            .iter()
+            .map(|(id, data)| Entry {
+                id: *id,
+                data: data.clone(),
+            })
+            .collect();
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&tuples, &mut buf, 0).unwrap();
+        let view = SolidityVecView::<(u32, Bytes)>::new(&buf, 0).unwrap();
+        assert_eq!(view.get(1).unwrap().as_ref(), Some(&tuples[1]));
+        let elements: Vec<_> = view.iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(elements, tuples);
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&entries, &mut buf, 0).unwrap();
+        let view = SolidityVecView::<Entry>::new(&buf, 0).unwrap();
+        assert_eq!(view.get(2).unwrap().as_ref(), Some(&entries[2]));
+        let elements: Vec<_> = view.iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(elements, entries);
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&tuples, &mut buf, 0).unwrap();
+        let view = FluentVecView::<(u32, Bytes)>::new(&buf, 0).unwrap();
+        assert_eq!(view.get(1).unwrap().as_ref(), Some(&tuples[1]));
+        let elements: Vec<_> = view.iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(elements, tuples);
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&entries, &mut buf, 0).unwrap();
+        let view = FluentVecView::<Entry>::new(&buf, 0).unwrap();
+        assert_eq!(view.get(2).unwrap().as_ref(), Some(&entries[2]));
+        let elements: Vec<_> = view.iter().collect::<Result<_, _>>().unwrap();
+        assert_eq!(elements, entries);
+    }
+
+    #[test]
+    fn test_decode_element() {
+        let original: Vec<U256> = (0..10u64).map(U256::from).collect();
+
+        let mut buf = BytesMut::new();
+        SolidityABI::encode(&original, &mut buf, 0).unwrap();
+        assert_eq!(
+            SolidityABI::<U256>::decode_element(&buf, 0, 7).unwrap(),
+            U256::from(7)
+        );
+        assert!(SolidityABI::<U256>::decode_element(&buf, 0, 10).is_err());
+
+        let mut buf = BytesMut::new();
+        FluentABI::encode(&original, &mut buf, 0).unwrap();
+        assert_eq!(
+            decode_element::<U256, LE, 4, false>(&buf, 0, 3).unwrap(),
+            U256::from(3)
+        );
+    }
+}